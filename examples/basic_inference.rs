@@ -29,6 +29,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             ..Default::default()
         },
         request_id: Some("example-001".to_string()),
+        ..Default::default()
     };
 
     // Run inference
@@ -37,6 +38,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             println!("\nGenerated text:");
             println!("{}", response.text);
             println!("\nMetrics:");
+            println!("  Prompt tokens: {}", response.usage.prompt_tokens);
             println!("  Tokens generated: {}", response.tokens_generated);
             println!("  Inference time: {:.2}s", response.inference_time);
             println!("  Tokens/sec: {:.1}", response.tokens_per_second);
@@ -3,8 +3,17 @@
 //! All inference backends must implement the `InferenceBackend` trait,
 //! providing a unified interface regardless of the underlying engine.
 
-use crate::error::Result;
-use crate::types::{InferenceRequest, InferenceResponse, ModelConfig};
+use crate::error::{AxonError, Result};
+use crate::types::{
+    BatchInferenceRequest, BatchInferenceResponse, ChatRequest, InferenceChunk, InferenceRequest, InferenceResponse,
+    ModelConfig,
+};
+use futures_util::Stream;
+use std::fmt;
+use std::future::Future;
+use std::os::unix::process::ExitStatusExt;
+use std::pin::Pin;
+use std::process::ExitStatus;
 
 /// Health status of a backend
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -16,7 +25,38 @@ pub enum HealthStatus {
     /// Backend is unhealthy but may recover
     Degraded,
     /// Backend has failed and cannot recover
-    Failed,
+    ///
+    /// Carries the engine's exit status when its process has exited.
+    Failed(Option<ProcessExit>),
+}
+
+/// How an engine process ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ProcessExit {
+    /// Exit code, if the process exited on its own
+    pub code: Option<i32>,
+
+    /// Signal that killed the process, if any
+    pub signal: Option<i32>,
+}
+
+impl From<ExitStatus> for ProcessExit {
+    fn from(status: ExitStatus) -> Self {
+        Self {
+            code: status.code(),
+            signal: status.signal(),
+        }
+    }
+}
+
+impl fmt::Display for ProcessExit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.code, self.signal) {
+            (Some(code), _) => write!(f, "exit code {}", code),
+            (None, Some(signal)) => write!(f, "killed by signal {}", signal),
+            (None, None) => f.write_str("unknown exit status"),
+        }
+    }
 }
 
 /// Metrics reported by a backend
@@ -39,6 +79,37 @@ pub struct BackendMetrics {
 
     /// GPU utilization percentage (0-100)
     pub gpu_utilization_percent: Option<f32>,
+
+    /// Requests the engine is currently decoding
+    pub running_requests: Option<u64>,
+
+    /// Requests queued inside the engine, waiting to be scheduled
+    pub queue_depth: Option<u64>,
+
+    /// KV-cache utilization percentage (0-100)
+    pub kv_cache_usage_percent: Option<f32>,
+
+    /// Fraction of prompt tokens served from the prefix cache (0-1)
+    pub prefix_cache_hit_rate: Option<f32>,
+
+    /// End-to-end latency of recent successful requests
+    pub latency: Option<LatencyPercentiles>,
+
+    /// Time to first token of recent streamed requests
+    pub time_to_first_token: Option<LatencyPercentiles>,
+}
+
+/// Latency distribution summary, in seconds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LatencyPercentiles {
+    /// Median
+    pub p50: f64,
+
+    /// 95th percentile
+    pub p95: f64,
+
+    /// 99th percentile
+    pub p99: f64,
 }
 
 impl BackendMetrics {
@@ -51,6 +122,12 @@ impl BackendMetrics {
             average_tps: 0.0,
             memory_usage_percent: None,
             gpu_utilization_percent: None,
+            running_requests: None,
+            queue_depth: None,
+            kv_cache_usage_percent: None,
+            prefix_cache_hit_rate: None,
+            latency: None,
+            time_to_first_token: None,
         }
     }
 }
@@ -61,6 +138,9 @@ impl Default for BackendMetrics {
     }
 }
 
+/// Stream of incremental output produced by `InferenceBackend::infer_stream`
+pub type InferenceStream = Pin<Box<dyn Stream<Item = Result<InferenceChunk>> + Send>>;
+
 /// Unified interface for all LLM inference backends
 ///
 /// This trait abstracts over different inference engines (vLLM, TGI, TensorRT-LLM),
@@ -97,6 +177,12 @@ impl Default for BackendMetrics {
 /// # Ok(())
 /// # }
 /// ```
+///
+/// # Concurrency
+///
+/// Every method returns a `Send` future, so generic callers can hand them to
+/// `tokio::spawn`. Implementations may still be written with `async fn`.
+/// Use [`DynBackend`] when the concrete backend is only known at runtime.
 pub trait InferenceBackend: Send + Sync {
     /// Load a model with the given configuration
     ///
@@ -113,7 +199,7 @@ pub trait InferenceBackend: Send + Sync {
     /// - Insufficient GPU memory
     /// - Backend process fails to start
     /// - Invalid configuration
-    async fn load_model(&mut self, config: ModelConfig) -> Result<()>;
+    fn load_model(&mut self, config: ModelConfig) -> impl Future<Output = Result<()>> + Send;
 
     /// Run inference on a single request
     ///
@@ -123,13 +209,54 @@ pub trait InferenceBackend: Send + Sync {
     /// - Backend is not ready (model not loaded)
     /// - Request is invalid
     /// - Backend fails during inference
-    async fn infer(&self, request: InferenceRequest) -> Result<InferenceResponse>;
+    fn infer(&self, request: InferenceRequest) -> impl Future<Output = Result<InferenceResponse>> + Send;
+
+    /// Run inference on a single request, yielding tokens as they are generated
+    ///
+    /// The stream ends after a chunk with `finished` set, or with an error
+    /// if the backend fails mid-generation. Dropping the stream abandons
+    /// the request.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the request could not be started; failures after
+    /// the first chunk are reported through the stream itself.
+    fn infer_stream(&self, request: InferenceRequest) -> impl Future<Output = Result<InferenceStream>> + Send;
+
+    /// Run a chat completion over a message history
+    ///
+    /// The backend renders `messages` with the model's own chat template,
+    /// so callers never format role markers into a prompt themselves.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if:
+    /// - Backend is not ready (model not loaded)
+    /// - The model has no chat template
+    /// - Backend fails during inference
+    fn chat(&self, request: ChatRequest) -> impl Future<Output = Result<InferenceResponse>> + Send;
+
+    /// Complete several prompts in one request, `sampling.n` times each
+    ///
+    /// Completions are returned ordered by prompt, each tagged with the index
+    /// of its prompt and its index among that prompt's completions.
+    ///
+    /// # Errors
+    ///
+    /// Only vLLM supports batches; the default implementation fails with
+    /// `InvalidConfig`.
+    fn infer_batch(
+        &self,
+        _request: BatchInferenceRequest,
+    ) -> impl Future<Output = Result<BatchInferenceResponse>> + Send {
+        async { Err(AxonError::InvalidConfig("batch inference is not supported by this backend".into())) }
+    }
 
     /// Check if the backend is healthy and ready
     ///
     /// Returns `HealthStatus::Healthy` if the backend can serve requests.
     /// Other statuses indicate varying degrees of unhealthiness.
-    async fn health_check(&self) -> HealthStatus;
+    fn health_check(&self) -> impl Future<Output = HealthStatus> + Send;
 
     /// Get current metrics from the backend
     ///
@@ -147,5 +274,165 @@ pub trait InferenceBackend: Send + Sync {
     /// # Errors
     ///
     /// Returns an error if shutdown fails, but attempts best-effort cleanup.
-    async fn shutdown(&mut self) -> Result<()>;
+    fn shutdown(&mut self) -> impl Future<Output = Result<()>> + Send;
+}
+
+/// Boxed `Send` future used by the object-safe backend interface
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Object-safe mirror of `InferenceBackend` with boxed futures
+trait ErasedBackend: Send + Sync {
+    fn boxed_load_model(&mut self, config: ModelConfig) -> BoxFuture<'_, Result<()>>;
+    fn boxed_infer(&self, request: InferenceRequest) -> BoxFuture<'_, Result<InferenceResponse>>;
+    fn boxed_infer_stream(&self, request: InferenceRequest) -> BoxFuture<'_, Result<InferenceStream>>;
+    fn boxed_chat(&self, request: ChatRequest) -> BoxFuture<'_, Result<InferenceResponse>>;
+    fn boxed_infer_batch(&self, request: BatchInferenceRequest) -> BoxFuture<'_, Result<BatchInferenceResponse>>;
+    fn boxed_health_check(&self) -> BoxFuture<'_, HealthStatus>;
+    fn boxed_metrics(&self) -> BackendMetrics;
+    fn boxed_shutdown(&mut self) -> BoxFuture<'_, Result<()>>;
+}
+
+impl<T: InferenceBackend> ErasedBackend for T {
+    fn boxed_load_model(&mut self, config: ModelConfig) -> BoxFuture<'_, Result<()>> {
+        Box::pin(InferenceBackend::load_model(self, config))
+    }
+
+    fn boxed_infer(&self, request: InferenceRequest) -> BoxFuture<'_, Result<InferenceResponse>> {
+        Box::pin(InferenceBackend::infer(self, request))
+    }
+
+    fn boxed_infer_stream(&self, request: InferenceRequest) -> BoxFuture<'_, Result<InferenceStream>> {
+        Box::pin(InferenceBackend::infer_stream(self, request))
+    }
+
+    fn boxed_chat(&self, request: ChatRequest) -> BoxFuture<'_, Result<InferenceResponse>> {
+        Box::pin(InferenceBackend::chat(self, request))
+    }
+
+    fn boxed_infer_batch(&self, request: BatchInferenceRequest) -> BoxFuture<'_, Result<BatchInferenceResponse>> {
+        Box::pin(InferenceBackend::infer_batch(self, request))
+    }
+
+    fn boxed_health_check(&self) -> BoxFuture<'_, HealthStatus> {
+        Box::pin(InferenceBackend::health_check(self))
+    }
+
+    fn boxed_metrics(&self) -> BackendMetrics {
+        InferenceBackend::metrics(self)
+    }
+
+    fn boxed_shutdown(&mut self) -> BoxFuture<'_, Result<()>> {
+        Box::pin(InferenceBackend::shutdown(self))
+    }
+}
+
+/// Type-erased backend
+///
+/// Wraps any `InferenceBackend` behind a vtable so the concrete engine can be
+/// chosen at runtime (see `BackendRegistry`). `DynBackend` itself implements
+/// `InferenceBackend`, so it drops into generic code unchanged.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axon::{DynBackend, InferenceBackend, TgiBackend, VllmBackend};
+///
+/// # async fn example(use_tgi: bool) {
+/// let backend = if use_tgi {
+///     DynBackend::new(TgiBackend::new())
+/// } else {
+///     DynBackend::new(VllmBackend::new())
+/// };
+///
+/// let health = tokio::spawn(async move { backend.health_check().await });
+/// # }
+/// ```
+pub struct DynBackend {
+    inner: Box<dyn ErasedBackend>,
+}
+
+impl DynBackend {
+    /// Erase the type of a backend
+    pub fn new<B: InferenceBackend + 'static>(backend: B) -> Self {
+        Self {
+            inner: Box::new(backend),
+        }
+    }
+}
+
+impl std::fmt::Debug for DynBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DynBackend").finish_non_exhaustive()
+    }
+}
+
+impl InferenceBackend for DynBackend {
+    fn load_model(&mut self, config: ModelConfig) -> impl Future<Output = Result<()>> + Send {
+        self.inner.boxed_load_model(config)
+    }
+
+    fn infer(&self, request: InferenceRequest) -> impl Future<Output = Result<InferenceResponse>> + Send {
+        self.inner.boxed_infer(request)
+    }
+
+    fn infer_stream(&self, request: InferenceRequest) -> impl Future<Output = Result<InferenceStream>> + Send {
+        self.inner.boxed_infer_stream(request)
+    }
+
+    fn chat(&self, request: ChatRequest) -> impl Future<Output = Result<InferenceResponse>> + Send {
+        self.inner.boxed_chat(request)
+    }
+
+    fn infer_batch(&self, request: BatchInferenceRequest) -> impl Future<Output = Result<BatchInferenceResponse>> + Send {
+        self.inner.boxed_infer_batch(request)
+    }
+
+    fn health_check(&self) -> impl Future<Output = HealthStatus> + Send {
+        self.inner.boxed_health_check()
+    }
+
+    fn metrics(&self) -> BackendMetrics {
+        self.inner.boxed_metrics()
+    }
+
+    fn shutdown(&mut self) -> impl Future<Output = Result<()>> + Send {
+        self.inner.boxed_shutdown()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vllm::VllmBackend;
+
+    #[tokio::test]
+    async fn test_dyn_backend_is_spawnable() {
+        let backend = DynBackend::new(VllmBackend::new());
+
+        let status = tokio::spawn(async move { backend.health_check().await })
+            .await
+            .unwrap();
+        assert_eq!(status, HealthStatus::Starting);
+    }
+
+    #[tokio::test]
+    async fn test_dyn_backend_forwards_calls() {
+        let backends: Vec<DynBackend> = vec![
+            DynBackend::new(VllmBackend::new()),
+            DynBackend::new(crate::tgi::TgiBackend::new()),
+        ];
+
+        for backend in &backends {
+            let result = backend.infer(InferenceRequest::default()).await;
+            assert!(matches!(result, Err(crate::AxonError::BackendNotRunning)));
+        }
+
+        // Batches reach vLLM; TGI falls back to the default rejection
+        let batch = BatchInferenceRequest {
+            prompts: vec!["a".to_string()],
+            ..Default::default()
+        };
+        assert!(matches!(backends[0].infer_batch(batch.clone()).await, Err(AxonError::BackendNotRunning)));
+        assert!(matches!(backends[1].infer_batch(batch).await, Err(AxonError::InvalidConfig(_))));
+    }
 }
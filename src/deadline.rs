@@ -0,0 +1,158 @@
+//! Per-request timeouts and cancellation
+//!
+//! A request ends early when its timeout elapses or its `CancellationToken`
+//! is cancelled. Either way the in-flight HTTP future or response stream is
+//! dropped, which closes the connection; the engine notices the disconnect
+//! and aborts the sequence, freeing its KV cache.
+
+use crate::backend::InferenceStream;
+use crate::error::{AxonError, Result};
+use crate::types::InferenceChunk;
+use futures_util::future::BoxFuture;
+use futures_util::{FutureExt, Stream};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::time::{sleep_until, Instant};
+use tokio_util::sync::CancellationToken;
+
+/// Time limit for requests that don't set `timeout`
+pub(crate) const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Time limit for establishing a connection to an engine
+pub(crate) const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Time limit for health checks and other control-plane calls
+pub(crate) const CONTROL_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Deadline and cancellation token of one request
+#[derive(Debug, Clone)]
+pub(crate) struct RequestLimits {
+    timeout: Duration,
+    deadline: Instant,
+    cancel: Option<CancellationToken>,
+}
+
+impl RequestLimits {
+    /// Limits starting now, from a request's `timeout` and `cancel` fields
+    pub(crate) fn new(timeout: Option<Duration>, cancel: Option<&CancellationToken>) -> Self {
+        let timeout = timeout.unwrap_or(DEFAULT_REQUEST_TIMEOUT);
+        Self {
+            timeout,
+            deadline: Instant::now() + timeout,
+            cancel: cancel.cloned(),
+        }
+    }
+
+    /// Resolves with the error that ends the request once a limit is hit
+    pub(crate) async fn expired(self) -> AxonError {
+        let cancelled = async {
+            match &self.cancel {
+                Some(token) => token.cancelled().await,
+                None => std::future::pending().await,
+            }
+        };
+
+        tokio::select! {
+            _ = sleep_until(self.deadline) => {
+                AxonError::Timeout(format!("request exceeded its {:?} timeout", self.timeout))
+            }
+            _ = cancelled => AxonError::Cancelled,
+        }
+    }
+
+    /// Run `fut` unless a limit is hit first, in which case it is dropped
+    pub(crate) async fn run<T>(&self, fut: impl Future<Output = Result<T>>) -> Result<T> {
+        tokio::select! {
+            result = fut => result,
+            err = self.clone().expired() => Err(err),
+        }
+    }
+
+    /// Open a stream with `fut` unless a limit is hit first, then bound the
+    /// stream by the same limits
+    pub(crate) async fn run_stream(self, fut: impl Future<Output = Result<InferenceStream>>) -> Result<InferenceStream> {
+        let stream = self.run(fut).await?;
+        Ok(self.bound_stream(stream))
+    }
+
+    /// Wrap a stream so it ends with an error once a limit is hit
+    pub(crate) fn bound_stream(self, stream: InferenceStream) -> InferenceStream {
+        Box::pin(BoundedStream {
+            inner: Some(stream),
+            expired: self.expired().boxed(),
+        })
+    }
+}
+
+/// Stream adapter that drops its inner stream when a limit is hit
+struct BoundedStream {
+    inner: Option<InferenceStream>,
+    expired: BoxFuture<'static, AxonError>,
+}
+
+impl Stream for BoundedStream {
+    type Item = Result<InferenceChunk>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.inner.is_none() {
+            return Poll::Ready(None);
+        }
+        if let Poll::Ready(err) = self.expired.as_mut().poll(cx) {
+            // Dropping the response body closes the connection
+            self.inner = None;
+            return Poll::Ready(Some(Err(err)));
+        }
+
+        let item = self.inner.as_mut().map(|inner| inner.as_mut().poll_next(cx));
+        match item {
+            Some(Poll::Ready(None)) => {
+                self.inner = None;
+                Poll::Ready(None)
+            }
+            Some(poll) => poll,
+            None => Poll::Ready(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+
+    #[tokio::test]
+    async fn test_timeout_and_cancellation() {
+        let limits = RequestLimits::new(Some(Duration::from_millis(20)), None);
+        let result = limits.run(std::future::pending::<Result<()>>()).await;
+        assert!(matches!(result, Err(AxonError::Timeout(_))));
+
+        let token = CancellationToken::new();
+        let limits = RequestLimits::new(None, Some(&token));
+        token.cancel();
+        let result = limits.run(std::future::pending::<Result<()>>()).await;
+        assert!(matches!(result, Err(AxonError::Cancelled)));
+    }
+
+    #[tokio::test]
+    async fn test_stream_ends_with_error_when_cancelled() {
+        let token = CancellationToken::new();
+        let chunk = InferenceChunk {
+            text_delta: "a".to_string(),
+            finished: false,
+            finish_reason: None,
+            usage: None,
+            logprobs: None,
+        };
+        let inner: InferenceStream = Box::pin(
+            futures_util::stream::iter([Ok(chunk)]).chain(futures_util::stream::pending()),
+        );
+        let mut stream = RequestLimits::new(None, Some(&token)).bound_stream(inner);
+
+        assert!(stream.next().await.unwrap().is_ok());
+        token.cancel();
+        assert!(matches!(stream.next().await, Some(Err(AxonError::Cancelled))));
+        assert!(stream.next().await.is_none());
+    }
+}
@@ -3,85 +3,215 @@
 //! Unified error types that can represent failures from any backend.
 
 use std::fmt;
+use std::time::Duration;
 
 /// Result type for Axon operations
 pub type Result<T> = std::result::Result<T, AxonError>;
 
 /// Errors that can occur in Axon
-#[derive(Debug)]
+#[derive(Debug, thiserror::Error)]
 pub enum AxonError {
     /// Model configuration is invalid
+    #[error("Invalid configuration: {0}")]
     InvalidConfig(String),
 
     /// Model failed to load
+    #[error("Model load failed: {0}")]
     ModelLoadFailed(String),
 
     /// Inference request failed
+    #[error("Inference failed: {0}")]
     InferenceFailed(String),
 
     /// Backend process is not running
+    #[error("Backend process is not running")]
     BackendNotRunning,
 
     /// Health check failed
+    #[error("Backend unhealthy: {0}")]
     Unhealthy(String),
 
     /// I/O error
-    IoError(std::io::Error),
+    #[error("I/O error: {0}")]
+    IoError(#[from] std::io::Error),
 
-    /// HTTP client error
-    HttpError(String),
+    /// The engine's API answered with an error status
+    #[error(transparent)]
+    Api(#[from] ApiError),
+
+    /// The request could not be sent or its response could not be read
+    #[error("HTTP error: {0}")]
+    HttpError(#[from] reqwest::Error),
 
     /// Timeout occurred
+    #[error("Timeout: {0}")]
     Timeout(String),
 
+    /// The request was cancelled through its `CancellationToken`
+    #[error("Request was cancelled")]
+    Cancelled,
+
     /// Backend-specific error
+    #[error("Backend error: {0}")]
     BackendError(String),
 
     /// Unknown or uncategorized error
+    #[error("Error: {0}")]
     Other(String),
 }
 
-impl fmt::Display for AxonError {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl AxonError {
+    /// Whether the failure is transient, so the same request may succeed if
+    /// retried later
+    ///
+    /// Connection failures, HTTP timeouts, rate limits, 5xx statuses and a
+    /// backend whose engine is (re)starting are retryable. Invalid requests
+    /// and configuration errors are not.
+    pub fn is_retryable(&self) -> bool {
         match self {
-            Self::InvalidConfig(msg) => write!(f, "Invalid configuration: {}", msg),
-            Self::ModelLoadFailed(msg) => write!(f, "Model load failed: {}", msg),
-            Self::InferenceFailed(msg) => write!(f, "Inference failed: {}", msg),
-            Self::BackendNotRunning => write!(f, "Backend process is not running"),
-            Self::Unhealthy(msg) => write!(f, "Backend unhealthy: {}", msg),
-            Self::IoError(err) => write!(f, "I/O error: {}", err),
-            Self::HttpError(msg) => write!(f, "HTTP error: {}", msg),
-            Self::Timeout(msg) => write!(f, "Timeout: {}", msg),
-            Self::BackendError(msg) => write!(f, "Backend error: {}", msg),
-            Self::Other(msg) => write!(f, "Error: {}", msg),
+            Self::Api(err) => err.is_retryable(),
+            Self::HttpError(err) => err.is_connect() || err.is_timeout(),
+            Self::BackendNotRunning => true,
+            _ => false,
         }
     }
 }
 
-impl std::error::Error for AxonError {
-    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        match self {
-            Self::IoError(err) => Some(err),
-            _ => None,
+/// Broad category of an API error
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiErrorKind {
+    /// The prompt plus requested tokens exceed the model's context window
+    ContextLengthExceeded,
+    /// The request was malformed or used an unsupported parameter value
+    InvalidRequest,
+    /// Missing or rejected credentials
+    Unauthorized,
+    /// Unknown endpoint or model
+    NotFound,
+    /// Too many requests
+    RateLimited,
+    /// The engine is overloaded or not ready (503)
+    Overloaded,
+    /// Other 5xx failure inside the engine
+    ServerError,
+    /// Any other status
+    Other,
+}
+
+/// Error status and body returned by an engine's HTTP API
+///
+/// Bodies in the OpenAI format used by vLLM (`{"error": {"message": ...}}`
+/// or the older flat `{"message": ..., "type": ...}`) and the
+/// `{"error": "...", "error_type": ...}` format used by TGI and Triton are
+/// parsed; anything else is kept verbatim as the message.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApiError {
+    /// HTTP status code
+    pub status: u16,
+
+    /// Classification of the failure
+    pub kind: ApiErrorKind,
+
+    /// Error message from the body
+    pub message: String,
+
+    /// Engine-specific error type (e.g., `BadRequestError` or `validation`)
+    pub error_type: Option<String>,
+
+    /// Request parameter the error refers to, if reported
+    pub param: Option<String>,
+
+    /// Delay requested by a `Retry-After` header, in whole seconds
+    pub retry_after: Option<Duration>,
+}
+
+impl ApiError {
+    /// Build an error from a response status and body
+    pub fn from_response(status: u16, body: &str) -> Self {
+        let json: Option<serde_json::Value> = serde_json::from_str(body).ok();
+        let root = json.as_ref();
+        let detail = root.and_then(|v| v.get("error").filter(|e| e.is_object())).or(root);
+        let field = |value: Option<&serde_json::Value>, key: &str| {
+            value.and_then(|v| v.get(key)).and_then(|v| v.as_str()).map(str::to_string)
+        };
+
+        let message = field(detail, "message")
+            .or_else(|| field(root, "error"))
+            .unwrap_or_else(|| body.trim().to_string());
+        let error_type = field(detail, "type").or_else(|| field(root, "error_type"));
+        let param = field(detail, "param");
+
+        Self {
+            status,
+            kind: ApiErrorKind::classify(status, &message),
+            message,
+            error_type,
+            param,
+            retry_after: None,
         }
     }
+
+    /// Whether retrying the same request may succeed
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self.kind,
+            ApiErrorKind::RateLimited | ApiErrorKind::Overloaded | ApiErrorKind::ServerError
+        )
+    }
 }
 
-impl From<std::io::Error> for AxonError {
-    fn from(err: std::io::Error) -> Self {
-        Self::IoError(err)
+impl ApiErrorKind {
+    fn classify(status: u16, message: &str) -> Self {
+        let message = message.to_ascii_lowercase();
+        let context_overflow = ["context length", "maximum context", "max_model_len", "max_new_tokens` must be", "too long"]
+            .iter()
+            .any(|p| message.contains(p));
+
+        match status {
+            400 | 413 | 422 if context_overflow => Self::ContextLengthExceeded,
+            400 | 413 | 422 => Self::InvalidRequest,
+            401 | 403 => Self::Unauthorized,
+            404 => Self::NotFound,
+            429 => Self::RateLimited,
+            503 => Self::Overloaded,
+            500..=599 => Self::ServerError,
+            _ => Self::Other,
+        }
     }
 }
 
-impl From<reqwest::Error> for AxonError {
-    fn from(err: reqwest::Error) -> Self {
-        Self::HttpError(err.to_string())
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "API error ({}): {}", self.status, self.message)
     }
 }
 
+impl std::error::Error for ApiError {}
+
+/// Pass a successful response through, turning error statuses into `ApiError`
+pub(crate) async fn check_status(resp: reqwest::Response) -> Result<reqwest::Response> {
+    let status = resp.status();
+    if status.is_success() {
+        return Ok(resp);
+    }
+
+    // HTTP-date values are rare from inference servers and are ignored
+    let retry_after = resp.headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse().ok())
+        .map(Duration::from_secs);
+
+    let body = resp.text().await.unwrap_or_default();
+    let mut err = ApiError::from_response(status.as_u16(), &body);
+    err.retry_after = retry_after;
+    Err(err.into())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::error::Error;
 
     #[test]
     fn test_error_display() {
@@ -97,5 +227,40 @@ mod tests {
         let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "file not found");
         let axon_err: AxonError = io_err.into();
         assert!(matches!(axon_err, AxonError::IoError(_)));
+        assert!(axon_err.source().is_some());
+    }
+
+    #[test]
+    fn test_api_error_bodies() {
+        let vllm = ApiError::from_response(
+            400,
+            r#"{"object":"error","message":"This model's maximum context length is 4096 tokens.","type":"BadRequestError","param":null,"code":400}"#,
+        );
+        assert_eq!(vllm.kind, ApiErrorKind::ContextLengthExceeded);
+        assert_eq!(vllm.error_type.as_deref(), Some("BadRequestError"));
+
+        let nested = ApiError::from_response(
+            400,
+            r#"{"error":{"message":"temperature must be non-negative","type":"BadRequestError","param":"temperature"}}"#,
+        );
+        assert_eq!(nested.kind, ApiErrorKind::InvalidRequest);
+        assert_eq!(nested.param.as_deref(), Some("temperature"));
+
+        let tgi = ApiError::from_response(429, r#"{"error":"Model is overloaded","error_type":"overloaded"}"#);
+        assert_eq!(tgi.message, "Model is overloaded");
+        assert_eq!(tgi.error_type.as_deref(), Some("overloaded"));
+
+        let plain = ApiError::from_response(502, "Bad Gateway\n");
+        assert_eq!(plain.message, "Bad Gateway");
+        assert_eq!(plain.kind, ApiErrorKind::ServerError);
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(AxonError::from(ApiError::from_response(503, "")).is_retryable());
+        assert!(AxonError::from(ApiError::from_response(429, "")).is_retryable());
+        assert!(!AxonError::from(ApiError::from_response(400, "bad")).is_retryable());
+        assert!(AxonError::BackendNotRunning.is_retryable());
+        assert!(!AxonError::InvalidConfig("x".into()).is_retryable());
     }
 }
@@ -17,17 +17,72 @@ pub mod error;
 /// vLLM backend implementation
 pub mod vllm;
 
+/// Text Generation Inference (TGI) backend implementation
+pub mod tgi;
+
+/// TensorRT-LLM backend implementation via Triton
+pub mod triton;
+
+/// Live request metrics tracking
+pub mod metrics;
+
+/// Engine stdout/stderr capture and log sinks
+pub mod logs;
+
+/// Runtime backend selection from configuration strings
+pub mod registry;
+
+/// Retry policies for transient request failures
+pub mod retry;
+
+/// Per-request timeouts and cancellation
+pub(crate) mod deadline;
+
+/// Spawned engine processes
+pub(crate) mod process;
+
+/// Server-sent events decoding for streaming responses
+pub(crate) mod sse;
+
+/// OpenAI-compatible wire types shared by HTTP backends
+pub(crate) mod openai;
+
+/// Prometheus text format parsing for engine metrics
+pub(crate) mod prometheus;
+
 /// Re-export the backend trait and common types
-pub use backend::{InferenceBackend, HealthStatus, BackendMetrics};
+pub use backend::{
+    InferenceBackend, InferenceStream, DynBackend, HealthStatus, ProcessExit, BackendMetrics, LatencyPercentiles,
+};
+
+/// Re-export the backend registry
+pub use registry::BackendRegistry;
+
+/// Re-export the retry policy
+pub use retry::RetryPolicy;
+
+/// Re-export the token used to cancel requests
+pub use tokio_util::sync::CancellationToken;
 
 /// Re-export vLLM backend for convenience
 pub use vllm::VllmBackend;
 
+/// Re-export TGI backend for convenience
+pub use tgi::TgiBackend;
+
+/// Re-export Triton backend for convenience
+pub use triton::TritonBackend;
+
 /// Re-export error types
-pub use error::{AxonError, Result};
+pub use error::{ApiError, ApiErrorKind, AxonError, Result};
 
 /// Common request/response types shared across backends
 pub mod types {
+    use crate::error::{AxonError, Result};
+    use std::collections::BTreeMap;
+    use std::time::Duration;
+    use tokio_util::sync::CancellationToken;
+
     /// Configuration for loading a model
     #[derive(Debug, Clone)]
     pub struct ModelConfig {
@@ -37,7 +92,9 @@ pub mod types {
         /// Number of GPUs for tensor parallelism
         pub tensor_parallel_size: Option<usize>,
 
-        /// Maximum number of tokens in a batch
+        /// Maximum concurrent sequences
+        ///
+        /// `None` leaves the engine's own default in place.
         pub max_batch_size: Option<usize>,
 
         /// Maximum sequence length
@@ -50,6 +107,8 @@ pub mod types {
         pub host: Option<String>,
 
         /// Port for the inference server API
+        ///
+        /// For vLLM, `None` picks a free ephemeral port.
         pub port: Option<u16>,
 
         /// Backend-specific configuration options
@@ -61,7 +120,7 @@ pub mod types {
             Self {
                 model_name: String::new(),
                 tensor_parallel_size: Some(1),
-                max_batch_size: Some(256),
+                max_batch_size: None,
                 max_sequence_length: Some(2048),
                 dtype: Some("auto".to_string()),
                 host: Some("127.0.0.1".to_string()),
@@ -72,7 +131,7 @@ pub mod types {
     }
 
     /// A single inference request
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, Default)]
     pub struct InferenceRequest {
         /// The input prompt(s)
         pub prompt: String,
@@ -82,16 +141,44 @@ pub mod types {
 
         /// Optional request ID for tracing
         pub request_id: Option<String>,
+
+        /// Served model or LoRA adapter to use instead of the loaded model
+        ///
+        /// vLLM routes it to any model or adapter it serves, and TGI to a
+        /// LoRA adapter (`adapter_id`). Triton rejects it.
+        pub model: Option<String>,
+
+        /// Time limit for the whole request, including retries and streaming
+        ///
+        /// Defaults to 120 seconds. Exceeding it fails with `AxonError::Timeout`.
+        pub timeout: Option<Duration>,
+
+        /// Token that aborts the request when cancelled
+        ///
+        /// Cancelling closes the connection, so the engine stops generating.
+        pub cancel: Option<CancellationToken>,
     }
 
-    impl Default for InferenceRequest {
-        fn default() -> Self {
-            Self {
-                prompt: String::new(),
-                sampling: SamplingParams::default(),
-                request_id: None,
-            }
-        }
+    /// Several prompts sampled with the same parameters in one call
+    #[derive(Debug, Clone, Default)]
+    pub struct BatchInferenceRequest {
+        /// The input prompts
+        pub prompts: Vec<String>,
+
+        /// Sampling strategy, applied to every prompt
+        pub sampling: SamplingParams,
+
+        /// Optional request ID for tracing
+        pub request_id: Option<String>,
+
+        /// Served model or LoRA adapter to use instead of the loaded model
+        pub model: Option<String>,
+
+        /// Time limit for the whole batch, including retries
+        pub timeout: Option<Duration>,
+
+        /// Token that aborts the batch when cancelled
+        pub cancel: Option<CancellationToken>,
     }
 
     /// Parameters controlling generation behavior
@@ -117,6 +204,56 @@ pub mod types {
 
         /// Stop sequences
         pub stop_sequences: Vec<String>,
+
+        /// Random seed, for reproducible sampling
+        pub seed: Option<u64>,
+
+        /// Minimum probability, relative to the most likely token, for a
+        /// token to be considered
+        pub min_p: Option<f32>,
+
+        /// Penalty for tokens already in the prompt or output (1.0 = none)
+        pub repetition_penalty: Option<f32>,
+
+        /// Minimum tokens to generate before EOS or stop sequences apply
+        pub min_tokens: Option<u32>,
+
+        /// Keep generating after the EOS token, up to `max_tokens`
+        pub ignore_eos: bool,
+
+        /// Bias added to the logits of specific token IDs
+        pub logit_bias: BTreeMap<u32, f32>,
+
+        /// Token IDs that stop generation, in addition to EOS
+        pub stop_token_ids: Vec<u32>,
+
+        /// Words that must never be generated
+        pub bad_words: Vec<String>,
+
+        /// Whether to omit special tokens from the output (engine default if unset)
+        pub skip_special_tokens: Option<bool>,
+
+        /// Include the matched stop sequence at the end of the output
+        pub include_stop_str_in_output: bool,
+
+        /// Return the log probability of each generated token, with this
+        /// many most likely alternatives per position (0 for none)
+        pub logprobs: Option<u32>,
+
+        /// Return the log probability of each prompt token, with this many
+        /// most likely alternatives per position
+        ///
+        /// Only `infer` can return them; streaming and chat reject it.
+        pub prompt_logprobs: Option<u32>,
+
+        /// Completions to return per prompt, at least 1
+        ///
+        /// Values above 1 require `InferenceBackend::infer_batch`.
+        pub n: u32,
+
+        /// Completions to generate per prompt, of which the `n` with the
+        /// highest cumulative logprob are returned; at least `n`
+        pub best_of: Option<u32>,
     }
 
     impl Default for SamplingParams {
@@ -129,30 +266,405 @@ pub mod types {
                 presence_penalty: Some(0.0),
                 frequency_penalty: Some(0.0),
                 stop_sequences: Vec::new(),
+                seed: None,
+                min_p: None,
+                repetition_penalty: None,
+                min_tokens: None,
+                ignore_eos: false,
+                logit_bias: BTreeMap::new(),
+                stop_token_ids: Vec::new(),
+                bad_words: Vec::new(),
+                skip_special_tokens: None,
+                include_stop_str_in_output: false,
+                logprobs: None,
+                prompt_logprobs: None,
+                n: 1,
+                best_of: None,
+            }
+        }
+    }
+
+    impl SamplingParams {
+        /// Names of the optional parameters set to something other than
+        /// their neutral value, for backends to check against what they
+        /// support
+        pub(crate) fn options_set(&self) -> Vec<&'static str> {
+            [
+                ("presence_penalty", self.presence_penalty.is_some_and(|p| p != 0.0)),
+                ("frequency_penalty", self.frequency_penalty.is_some_and(|p| p != 0.0)),
+                ("seed", self.seed.is_some()),
+                ("min_p", self.min_p.is_some_and(|p| p > 0.0)),
+                ("repetition_penalty", self.repetition_penalty.is_some_and(|p| p != 1.0)),
+                ("min_tokens", self.min_tokens.is_some_and(|n| n > 0)),
+                ("ignore_eos", self.ignore_eos),
+                ("logit_bias", !self.logit_bias.is_empty()),
+                ("stop_token_ids", !self.stop_token_ids.is_empty()),
+                ("bad_words", !self.bad_words.is_empty()),
+                ("skip_special_tokens", self.skip_special_tokens == Some(false)),
+                ("include_stop_str_in_output", self.include_stop_str_in_output),
+                ("logprobs", self.logprobs.is_some()),
+                ("prompt_logprobs", self.prompt_logprobs.is_some()),
+                ("n", self.n != 1),
+                ("best_of", self.best_of.is_some()),
+            ]
+            .into_iter()
+            .filter_map(|(name, set)| set.then_some(name))
+            .collect()
+        }
+
+        /// Reject options outside `supported` rather than silently dropping
+        /// them, naming `backend` in the error
+        pub(crate) fn check_supported(&self, backend: &str, supported: &[&str]) -> Result<()> {
+            match self.options_set().into_iter().find(|option| !supported.contains(option)) {
+                Some(option) => Err(AxonError::InvalidConfig(format!(
+                    "{} does not support {}",
+                    backend, option
+                ))),
+                None => Ok(()),
+            }
+        }
+    }
+
+    /// Author of a chat message
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub enum ChatRole {
+        /// Instructions that frame the conversation
+        System,
+        /// Input from the end user
+        User,
+        /// Output previously produced by the model
+        Assistant,
+        /// Result of a tool call made by the assistant
+        Tool,
+    }
+
+    impl ChatRole {
+        /// Role name as used by OpenAI-compatible APIs
+        pub fn as_str(&self) -> &'static str {
+            match self {
+                Self::System => "system",
+                Self::User => "user",
+                Self::Assistant => "assistant",
+                Self::Tool => "tool",
             }
         }
     }
 
+    /// One part of a multi-part message
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum ContentPart {
+        /// Plain text
+        Text(String),
+        /// Image referenced by URL (including `data:` URLs)
+        ImageUrl(String),
+    }
+
+    /// Content of a chat message
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum MessageContent {
+        /// A single text string
+        Text(String),
+        /// A sequence of text and media parts
+        Parts(Vec<ContentPart>),
+    }
+
+    impl From<String> for MessageContent {
+        fn from(text: String) -> Self {
+            Self::Text(text)
+        }
+    }
+
+    impl From<&str> for MessageContent {
+        fn from(text: &str) -> Self {
+            Self::Text(text.to_string())
+        }
+    }
+
+    /// A function call requested by the assistant
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct ToolCall {
+        /// ID that the answering tool message refers to
+        pub id: String,
+
+        /// Name of the function to call
+        pub name: String,
+
+        /// Arguments as a JSON-encoded object
+        pub arguments: String,
+    }
+
+    /// A function the model may call in a chat
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Tool {
+        /// Name the model calls the function by
+        pub name: String,
+
+        /// What the function does, to help the model decide when to call it
+        pub description: Option<String>,
+
+        /// JSON Schema of the function's arguments
+        pub parameters: serde_json::Value,
+    }
+
+    /// Whether and which tools the model must call
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum ToolChoice {
+        /// The model decides whether to call a tool
+        Auto,
+        /// The model must not call a tool
+        None,
+        /// The model must call at least one tool
+        Required,
+        /// The model must call the named function
+        Function(String),
+    }
+
+    /// A single message in a chat conversation
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct ChatMessage {
+        /// Who wrote the message
+        pub role: ChatRole,
+
+        /// Message body
+        pub content: MessageContent,
+
+        /// Optional participant name
+        pub name: Option<String>,
+
+        /// ID of the tool call this message answers (tool role only)
+        pub tool_call_id: Option<String>,
+
+        /// Tool calls the assistant made (assistant role only)
+        pub tool_calls: Vec<ToolCall>,
+    }
+
+    impl ChatMessage {
+        /// Create a message with the given role and content
+        pub fn new(role: ChatRole, content: impl Into<MessageContent>) -> Self {
+            Self {
+                role,
+                content: content.into(),
+                name: None,
+                tool_call_id: None,
+                tool_calls: Vec::new(),
+            }
+        }
+
+        /// Create a system message
+        pub fn system(content: impl Into<MessageContent>) -> Self {
+            Self::new(ChatRole::System, content)
+        }
+
+        /// Create a user message
+        pub fn user(content: impl Into<MessageContent>) -> Self {
+            Self::new(ChatRole::User, content)
+        }
+
+        /// Create an assistant message
+        pub fn assistant(content: impl Into<MessageContent>) -> Self {
+            Self::new(ChatRole::Assistant, content)
+        }
+
+        /// Create an assistant message that makes `tool_calls` without text
+        pub fn assistant_tool_calls(tool_calls: Vec<ToolCall>) -> Self {
+            Self {
+                tool_calls,
+                ..Self::new(ChatRole::Assistant, "")
+            }
+        }
+
+        /// Create a tool result message answering `tool_call_id`
+        pub fn tool(tool_call_id: impl Into<String>, content: impl Into<MessageContent>) -> Self {
+            Self {
+                tool_call_id: Some(tool_call_id.into()),
+                ..Self::new(ChatRole::Tool, content)
+            }
+        }
+    }
+
+    /// A chat completion request
+    ///
+    /// The backend applies the model's chat template to `messages`.
+    #[derive(Debug, Clone, Default)]
+    pub struct ChatRequest {
+        /// Conversation history, oldest first
+        pub messages: Vec<ChatMessage>,
+
+        /// Sampling strategy
+        pub sampling: SamplingParams,
+
+        /// Optional request ID for tracing
+        pub request_id: Option<String>,
+
+        /// Served model or LoRA adapter to use (see `InferenceRequest::model`)
+        pub model: Option<String>,
+
+        /// Functions the model may call
+        pub tools: Vec<Tool>,
+
+        /// Whether and which tools the model must call (engine default if unset)
+        pub tool_choice: Option<ToolChoice>,
+
+        /// Time limit for the whole request (see `InferenceRequest::timeout`)
+        pub timeout: Option<Duration>,
+
+        /// Token that aborts the request when cancelled
+        pub cancel: Option<CancellationToken>,
+    }
+
+    /// Token counts reported by the engine for one request
+    ///
+    /// Counts an engine does not report are left at zero: TGI's `/generate`
+    /// omits prompt tokens and Triton reports no counts at all.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct Usage {
+        /// Tokens in the prompt
+        pub prompt_tokens: usize,
+
+        /// Tokens generated
+        pub completion_tokens: usize,
+
+        /// Prompt tokens served from the prefix cache, if reported
+        pub cached_prompt_tokens: Option<usize>,
+    }
+
+    impl Usage {
+        /// Prompt and generated tokens combined
+        pub fn total_tokens(&self) -> usize {
+            self.prompt_tokens + self.completion_tokens
+        }
+
+        /// Generated tokens per second over `elapsed`
+        pub fn tokens_per_second(&self, elapsed: Duration) -> f32 {
+            if elapsed.is_zero() {
+                0.0
+            } else {
+                (self.completion_tokens as f64 / elapsed.as_secs_f64()) as f32
+            }
+        }
+    }
+
+    /// Log probability of one token of the prompt or output
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct TokenLogprob {
+        /// Token text
+        pub token: String,
+
+        /// Token ID, if the engine reports it
+        pub token_id: Option<u32>,
+
+        /// Natural log of the token's probability
+        pub logprob: f32,
+
+        /// Most likely tokens at this position, most likely first
+        pub top_logprobs: Vec<TopLogprob>,
+    }
+
+    /// An alternative token at one position
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct TopLogprob {
+        /// Token text
+        pub token: String,
+
+        /// Token ID, if the engine reports it
+        pub token_id: Option<u32>,
+
+        /// Natural log of the token's probability
+        pub logprob: f32,
+    }
+
     /// Response from an inference request
     #[derive(Debug, Clone)]
     pub struct InferenceResponse {
         /// Generated text
         pub text: String,
 
-        /// Number of tokens generated
+        /// Number of tokens generated (same as `usage.completion_tokens`)
         pub tokens_generated: usize,
 
+        /// Prompt and generated token counts, zero if the engine does not
+        /// report them (Triton)
+        pub usage: Usage,
+
         /// Time taken for inference (seconds)
         pub inference_time: f64,
 
         /// Tokens per second
         pub tokens_per_second: f32,
 
+        /// Finish reason ("length", "stop", "tool_calls", or "error"), or
+        /// "unknown" if the engine does not report it (Triton)
+        pub finish_reason: String,
+
+        /// Optional request ID (echoed back if provided)
+        pub request_id: Option<String>,
+
+        /// Attempts made, including retries (1 if the first attempt succeeded)
+        pub attempts: u32,
+
+        /// Tools the model called (chat only); answer each with
+        /// `ChatMessage::tool` using its `id`
+        pub tool_calls: Vec<ToolCall>,
+
+        /// Per-token log probabilities of the output, if requested
+        pub logprobs: Option<Vec<TokenLogprob>>,
+
+        /// Per-token log probabilities of the prompt, if requested
+        ///
+        /// The first prompt token has no preceding context, so entries start
+        /// at the second token.
+        pub prompt_logprobs: Option<Vec<TokenLogprob>>,
+    }
+
+    /// One completion of one prompt in a batch
+    #[derive(Debug, Clone)]
+    pub struct CompletionChoice {
+        /// Position of the prompt in `BatchInferenceRequest::prompts`
+        pub prompt_index: usize,
+
+        /// Position of this completion among the prompt's `n` completions
+        pub index: usize,
+
+        /// Generated text
+        pub text: String,
+
         /// Finish reason ("length", "stop", or "error")
         pub finish_reason: String,
 
+        /// Per-token log probabilities of the output, if requested
+        pub logprobs: Option<Vec<TokenLogprob>>,
+
+        /// Per-token log probabilities of the prompt, if requested
+        pub prompt_logprobs: Option<Vec<TokenLogprob>>,
+    }
+
+    /// Response to a `BatchInferenceRequest`
+    #[derive(Debug, Clone)]
+    pub struct BatchInferenceResponse {
+        /// All completions, ordered by prompt and then by index
+        pub choices: Vec<CompletionChoice>,
+
+        /// Token counts summed over the batch
+        pub usage: Usage,
+
+        /// Time taken for inference (seconds)
+        pub inference_time: f64,
+
+        /// Generated tokens per second, over the whole batch
+        pub tokens_per_second: f32,
+
         /// Optional request ID (echoed back if provided)
         pub request_id: Option<String>,
+
+        /// Attempts made, including retries (1 if the first attempt succeeded)
+        pub attempts: u32,
+    }
+
+    impl BatchInferenceResponse {
+        /// Completions of the prompt at `prompt_index`, in index order
+        pub fn for_prompt(&self, prompt_index: usize) -> impl Iterator<Item = &CompletionChoice> {
+            self.choices.iter().filter(move |c| c.prompt_index == prompt_index)
+        }
     }
 
     /// Streaming inference response chunk
@@ -166,11 +678,23 @@ pub mod types {
 
         /// Finish reason (only if finished)
         pub finish_reason: Option<String>,
+
+        /// Token counts so far, if the engine reports them while streaming
+        ///
+        /// vLLM includes them on every chunk; TGI only on the final one.
+        pub usage: Option<Usage>,
+
+        /// Log probabilities of the tokens in `text_delta`, if requested
+        pub logprobs: Option<Vec<TokenLogprob>>,
     }
 }
 
 // Re-export common types
-pub use types::{InferenceRequest, InferenceResponse, InferenceChunk, ModelConfig, SamplingParams};
+pub use types::{
+    BatchInferenceRequest, BatchInferenceResponse, ChatMessage, ChatRequest, ChatRole, CompletionChoice,
+    ContentPart, InferenceChunk, InferenceRequest, InferenceResponse, MessageContent, ModelConfig,
+    SamplingParams, TokenLogprob, Tool, ToolCall, ToolChoice, TopLogprob, Usage,
+};
 
 #[cfg(test)]
 mod tests {
@@ -180,7 +704,7 @@ mod tests {
     fn test_model_config_default() {
         let config = ModelConfig::default();
         assert_eq!(config.tensor_parallel_size, Some(1));
-        assert_eq!(config.max_batch_size, Some(256));
+        assert_eq!(config.max_batch_size, None);
     }
 
     #[test]
@@ -200,10 +724,22 @@ mod tests {
                 ..Default::default()
             },
             request_id: Some("test-123".to_string()),
+            ..Default::default()
         };
 
         assert_eq!(request.prompt, "Hello, world!");
         assert_eq!(request.sampling.max_tokens, 50);
         assert_eq!(request.request_id, Some("test-123".to_string()));
     }
+
+    #[test]
+    fn test_chat_message_constructors() {
+        let system = ChatMessage::system("Be brief.");
+        assert_eq!(system.role, ChatRole::System);
+        assert_eq!(system.content, MessageContent::Text("Be brief.".to_string()));
+
+        let tool = ChatMessage::tool("call-1", "42");
+        assert_eq!(tool.role.as_str(), "tool");
+        assert_eq!(tool.tool_call_id.as_deref(), Some("call-1"));
+    }
 }
@@ -0,0 +1,296 @@
+//! Engine log capture
+//!
+//! Inference engines write their logs to stdout/stderr. If nobody reads those
+//! pipes the OS buffer fills and the engine blocks on its next write, so
+//! spawned processes always have both streams drained into a `LogSink`.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// Which output stream a line came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogStream {
+    /// Standard output
+    Stdout,
+    /// Standard error
+    Stderr,
+}
+
+impl LogStream {
+    /// Stream name ("stdout" or "stderr")
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Stdout => "stdout",
+            Self::Stderr => "stderr",
+        }
+    }
+}
+
+/// Destination for engine output
+///
+/// Called from the task draining the pipe, once per line, so implementations
+/// should not block for long.
+pub trait LogSink: Send + Sync {
+    /// Handle one line of output, without its trailing newline
+    fn write_line(&self, stream: LogStream, line: &str);
+}
+
+impl fmt::Debug for dyn LogSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("LogSink")
+    }
+}
+
+/// Forwards engine output to `tracing` under the `axon::engine` target
+#[derive(Debug, Clone)]
+pub struct TracingSink {
+    engine: String,
+}
+
+impl TracingSink {
+    /// Create a sink that tags events with the engine name (e.g., "vllm")
+    pub fn new(engine: impl Into<String>) -> Self {
+        Self {
+            engine: engine.into(),
+        }
+    }
+}
+
+impl LogSink for TracingSink {
+    fn write_line(&self, stream: LogStream, line: &str) {
+        tracing::info!(target: "axon::engine", engine = %self.engine, stream = stream.as_str(), "{}", line);
+    }
+}
+
+/// Keeps the most recent lines in memory
+#[derive(Debug)]
+pub struct RingBufferSink {
+    capacity: usize,
+    lines: Mutex<VecDeque<String>>,
+}
+
+impl RingBufferSink {
+    /// Create a buffer holding at most `capacity` lines
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            lines: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    /// Snapshot of the buffered lines, oldest first
+    pub fn lines(&self) -> Vec<String> {
+        self.lines.lock().unwrap().iter().cloned().collect()
+    }
+}
+
+impl LogSink for RingBufferSink {
+    fn write_line(&self, _stream: LogStream, line: &str) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        let mut lines = self.lines.lock().unwrap();
+        if lines.len() == self.capacity {
+            lines.pop_front();
+        }
+        lines.push_back(line.to_string());
+    }
+}
+
+/// Appends output to a file, rotating it once it exceeds a size limit
+///
+/// On rotation `engine.log` becomes `engine.log.1`, `engine.log.1` becomes
+/// `engine.log.2`, and so on; files beyond `max_files` are deleted.
+#[derive(Debug)]
+pub struct RotatingFileSink {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    state: Mutex<FileState>,
+}
+
+#[derive(Debug)]
+struct FileState {
+    file: File,
+    written: u64,
+}
+
+impl RotatingFileSink {
+    /// Open (or create) `path` for appending
+    pub fn new(path: impl Into<PathBuf>, max_bytes: u64, max_files: usize) -> io::Result<Self> {
+        let path = path.into();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let written = file.metadata()?.len();
+
+        Ok(Self {
+            path,
+            max_bytes,
+            max_files,
+            state: Mutex::new(FileState { file, written }),
+        })
+    }
+
+    fn rotated_path(&self, index: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{}", index));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&self, state: &mut FileState) -> io::Result<()> {
+        if self.max_files == 0 {
+            state.file.set_len(0)?;
+        } else {
+            let _ = fs::remove_file(self.rotated_path(self.max_files));
+            for index in (1..self.max_files).rev() {
+                let _ = fs::rename(self.rotated_path(index), self.rotated_path(index + 1));
+            }
+            fs::rename(&self.path, self.rotated_path(1))?;
+            state.file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        }
+
+        state.written = 0;
+        Ok(())
+    }
+}
+
+impl LogSink for RotatingFileSink {
+    fn write_line(&self, _stream: LogStream, line: &str) {
+        let mut state = self.state.lock().unwrap();
+
+        if state.written > 0 && state.written + line.len() as u64 + 1 > self.max_bytes {
+            // Logging must never take the engine down; keep writing on failure
+            let _ = self.rotate(&mut state);
+        }
+
+        if writeln!(state.file, "{}", line).is_ok() {
+            state.written += line.len() as u64 + 1;
+        }
+    }
+}
+
+/// Fans lines out to several sinks
+#[derive(Debug, Default, Clone)]
+pub struct LogSinks(Vec<Arc<dyn LogSink>>);
+
+impl LogSinks {
+    /// Create an empty set of sinks
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a sink
+    pub fn push(&mut self, sink: Arc<dyn LogSink>) {
+        self.0.push(sink);
+    }
+}
+
+impl LogSink for LogSinks {
+    fn write_line(&self, stream: LogStream, line: &str) {
+        for sink in &self.0 {
+            sink.write_line(stream, line);
+        }
+    }
+}
+
+/// Read a pipe until EOF, forwarding each line to `sink`
+///
+/// Both `\n` and `\r` end a line, so progress bars that redraw in place are
+/// seen as they update rather than only when they finish.
+pub(crate) async fn drain<R>(mut reader: R, stream: LogStream, sink: Arc<dyn LogSink>)
+where
+    R: AsyncRead + Unpin,
+{
+    let mut buf = [0u8; 8192];
+    let mut pending = Vec::new();
+
+    loop {
+        let n = match reader.read(&mut buf).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => n,
+        };
+
+        for &byte in &buf[..n] {
+            if byte == b'\n' || byte == b'\r' {
+                emit(&mut pending, stream, sink.as_ref());
+            } else {
+                pending.push(byte);
+            }
+        }
+    }
+
+    emit(&mut pending, stream, sink.as_ref());
+}
+
+fn emit(pending: &mut Vec<u8>, stream: LogStream, sink: &dyn LogSink) {
+    if !pending.is_empty() {
+        sink.write_line(stream, &String::from_utf8_lossy(pending));
+        pending.clear();
+    }
+}
+
+/// Append recent engine output to an error message
+pub(crate) fn with_tail(message: String, tail: &[String]) -> String {
+    if tail.is_empty() {
+        return message;
+    }
+
+    format!(
+        "{}\n--- last {} lines of engine output ---\n{}",
+        message,
+        tail.len(),
+        tail.join("\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_buffer_keeps_latest() {
+        let ring = RingBufferSink::new(2);
+        for line in ["a", "b", "c"] {
+            ring.write_line(LogStream::Stderr, line);
+        }
+        assert_eq!(ring.lines(), vec!["b", "c"]);
+    }
+
+    #[tokio::test]
+    async fn test_drain_splits_lines_and_progress() {
+        let ring = Arc::new(RingBufferSink::new(10));
+        let input: &[u8] = b"INFO start\nLoading  10%\rLoading 100%\r\ntail";
+
+        drain(input, LogStream::Stderr, ring.clone()).await;
+        assert_eq!(ring.lines(), vec!["INFO start", "Loading  10%", "Loading 100%", "tail"]);
+    }
+
+    #[test]
+    fn test_rotating_file_sink() {
+        let dir = std::env::temp_dir().join(format!("axon-logs-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("engine.log");
+
+        let sink = RotatingFileSink::new(&path, 10, 2).unwrap();
+        for line in ["0123456789", "second", "third"] {
+            sink.write_line(LogStream::Stdout, line);
+        }
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "third\n");
+        assert_eq!(fs::read_to_string(dir.join("engine.log.1")).unwrap(), "second\n");
+        assert_eq!(fs::read_to_string(dir.join("engine.log.2")).unwrap(), "0123456789\n");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_with_tail() {
+        assert_eq!(with_tail("failed".into(), &[]), "failed");
+        assert!(with_tail("failed".into(), &["CUDA OOM".into()]).ends_with("CUDA OOM"));
+    }
+}
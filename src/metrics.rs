@@ -0,0 +1,362 @@
+//! Live request metrics
+//!
+//! `MetricsTracker` is shared by a backend and the requests it serves. Counters
+//! are atomics so `infer(&self)` can update them without locking; latency
+//! samples are kept in a bounded window for percentile estimates.
+
+use crate::backend::{BackendMetrics, InferenceStream, LatencyPercentiles};
+use crate::error::Result;
+use crate::types::{BatchInferenceResponse, InferenceChunk, InferenceResponse};
+use futures_util::Stream;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+/// Number of recent samples kept per latency window
+const WINDOW_SIZE: usize = 1024;
+
+/// Request counters and latency windows for one backend
+#[derive(Debug, Default)]
+pub struct MetricsTracker {
+    /// Requests started but not yet finished
+    pending: AtomicU64,
+
+    /// Requests started since creation
+    total: AtomicU64,
+
+    /// Requests that ended in an error
+    failed: AtomicU64,
+
+    /// Tokens generated by successful requests
+    tokens: AtomicU64,
+
+    /// Wall time spent on successful requests, in microseconds
+    busy_micros: AtomicU64,
+
+    /// End-to-end latency of successful requests
+    latency: LatencyWindow,
+
+    /// Time until the first streamed token
+    time_to_first_token: LatencyWindow,
+}
+
+impl MetricsTracker {
+    /// Create a tracker with all counters at zero
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Mark the start of a request
+    ///
+    /// The returned guard must be resolved with `succeed` or `fail`, or
+    /// handed the request through `run` or `run_stream`; if it is dropped
+    /// instead (the caller abandoned the request) only the pending
+    /// count is released.
+    pub fn start(self: &Arc<Self>) -> RequestGuard {
+        self.pending.fetch_add(1, Ordering::Relaxed);
+        self.total.fetch_add(1, Ordering::Relaxed);
+
+        RequestGuard {
+            tracker: Arc::clone(self),
+            started: Instant::now(),
+            done: false,
+        }
+    }
+
+    /// Take a consistent-enough snapshot for reporting
+    pub fn snapshot(&self) -> BackendMetrics {
+        let tokens = self.tokens.load(Ordering::Relaxed);
+        let busy = Duration::from_micros(self.busy_micros.load(Ordering::Relaxed));
+
+        BackendMetrics {
+            pending_requests: self.pending.load(Ordering::Relaxed),
+            total_requests: self.total.load(Ordering::Relaxed),
+            failed_requests: self.failed.load(Ordering::Relaxed),
+            average_tps: if busy.is_zero() {
+                0.0
+            } else {
+                (tokens as f64 / busy.as_secs_f64()) as f32
+            },
+            latency: self.latency.percentiles(),
+            time_to_first_token: self.time_to_first_token.percentiles(),
+            ..BackendMetrics::new()
+        }
+    }
+}
+
+/// In-flight request handle returned by `MetricsTracker::start`
+#[derive(Debug)]
+pub struct RequestGuard {
+    tracker: Arc<MetricsTracker>,
+    started: Instant,
+    done: bool,
+}
+
+impl RequestGuard {
+    /// Record a successful request that generated `tokens` tokens
+    pub fn succeed(mut self, tokens: usize) {
+        let elapsed = self.started.elapsed();
+        let tracker = &self.tracker;
+
+        tracker.tokens.fetch_add(tokens as u64, Ordering::Relaxed);
+        tracker.busy_micros.fetch_add(elapsed.as_micros() as u64, Ordering::Relaxed);
+        tracker.latency.record(elapsed);
+        self.release();
+    }
+
+    /// Record a failed request
+    pub fn fail(mut self) {
+        self.tracker.failed.fetch_add(1, Ordering::Relaxed);
+        self.release();
+    }
+
+    /// Await a complete response and record its outcome
+    pub(crate) async fn run<T: GeneratedTokens>(self, fut: impl Future<Output = Result<T>>) -> Result<T> {
+        let result = fut.await;
+        match &result {
+            Ok(response) => self.succeed(response.generated_tokens()),
+            Err(_) => self.fail(),
+        }
+        result
+    }
+
+    /// Await a token stream and track it, recording a failure if it could
+    /// not be opened
+    pub(crate) async fn run_stream(self, fut: impl Future<Output = Result<InferenceStream>>) -> Result<InferenceStream> {
+        match fut.await {
+            Ok(stream) => Ok(self.track_stream(stream)),
+            Err(e) => {
+                self.fail();
+                Err(e)
+            }
+        }
+    }
+
+    /// Wrap a token stream so its outcome and time-to-first-token are recorded
+    ///
+    /// Token counts come from the chunks' usage when the engine reports it;
+    /// otherwise each non-empty chunk counts as one generated token.
+    pub fn track_stream(self, stream: InferenceStream) -> InferenceStream {
+        Box::pin(TrackedStream {
+            inner: stream,
+            guard: Some(self),
+            tokens: 0,
+        })
+    }
+
+    fn release(&mut self) {
+        if !self.done {
+            self.done = true;
+            self.tracker.pending.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+}
+
+impl Drop for RequestGuard {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+/// Complete responses whose generated tokens `RequestGuard::run` records
+pub(crate) trait GeneratedTokens {
+    /// Number of tokens generated across the response
+    fn generated_tokens(&self) -> usize;
+}
+
+impl GeneratedTokens for InferenceResponse {
+    fn generated_tokens(&self) -> usize {
+        self.tokens_generated
+    }
+}
+
+impl GeneratedTokens for BatchInferenceResponse {
+    fn generated_tokens(&self) -> usize {
+        self.usage.completion_tokens
+    }
+}
+
+/// Stream adapter that reports to a `RequestGuard`
+struct TrackedStream {
+    inner: InferenceStream,
+    guard: Option<RequestGuard>,
+    tokens: usize,
+}
+
+impl Stream for TrackedStream {
+    type Item = Result<InferenceChunk>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let item = match self.inner.as_mut().poll_next(cx) {
+            Poll::Pending => return Poll::Pending,
+            Poll::Ready(item) => item,
+        };
+
+        match &item {
+            Some(Ok(chunk)) => {
+                if !chunk.text_delta.is_empty() {
+                    if self.tokens == 0
+                        && let Some(guard) = &self.guard
+                    {
+                        let ttft = guard.started.elapsed();
+                        guard.tracker.time_to_first_token.record(ttft);
+                    }
+                    self.tokens += 1;
+                }
+                if let Some(usage) = chunk.usage {
+                    self.tokens = usage.completion_tokens;
+                }
+                if chunk.finished {
+                    let tokens = self.tokens;
+                    if let Some(guard) = self.guard.take() {
+                        guard.succeed(tokens);
+                    }
+                }
+            }
+            Some(Err(_)) => {
+                if let Some(guard) = self.guard.take() {
+                    guard.fail();
+                }
+            }
+            None => {
+                let tokens = self.tokens;
+                if let Some(guard) = self.guard.take() {
+                    guard.succeed(tokens);
+                }
+            }
+        }
+
+        Poll::Ready(item)
+    }
+}
+
+/// Bounded window of recent latency samples
+#[derive(Debug, Default)]
+struct LatencyWindow {
+    samples: Mutex<VecDeque<f64>>,
+}
+
+impl LatencyWindow {
+    fn record(&self, elapsed: Duration) {
+        let mut samples = self.samples.lock().unwrap();
+        if samples.len() == WINDOW_SIZE {
+            samples.pop_front();
+        }
+        samples.push_back(elapsed.as_secs_f64());
+    }
+
+    fn percentiles(&self) -> Option<LatencyPercentiles> {
+        let mut sorted: Vec<f64> = self.samples.lock().unwrap().iter().copied().collect();
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_by(f64::total_cmp);
+
+        // Nearest-rank percentile
+        let rank = |p: f64| sorted[((p * sorted.len() as f64).ceil() as usize).clamp(1, sorted.len()) - 1];
+
+        Some(LatencyPercentiles {
+            p50: rank(0.50),
+            p95: rank(0.95),
+            p99: rank(0.99),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AxonError;
+    use futures_util::{stream, StreamExt};
+
+    fn chunk(text: &str, finished: bool) -> Result<InferenceChunk> {
+        Ok(InferenceChunk {
+            text_delta: text.to_string(),
+            finished,
+            finish_reason: finished.then(|| "stop".to_string()),
+            usage: None,
+            logprobs: None,
+        })
+    }
+
+    #[test]
+    fn test_counters() {
+        let tracker = MetricsTracker::new();
+
+        let ok = tracker.start();
+        let failed = tracker.start();
+        let abandoned = tracker.start();
+        assert_eq!(tracker.snapshot().pending_requests, 3);
+
+        ok.succeed(10);
+        failed.fail();
+        drop(abandoned);
+
+        let metrics = tracker.snapshot();
+        assert_eq!(metrics.pending_requests, 0);
+        assert_eq!(metrics.total_requests, 3);
+        assert_eq!(metrics.failed_requests, 1);
+        assert!(metrics.average_tps > 0.0);
+        assert!(metrics.latency.is_some());
+        assert!(metrics.time_to_first_token.is_none());
+    }
+
+    #[test]
+    fn test_percentiles() {
+        let window = LatencyWindow::default();
+        for ms in 1..=100 {
+            window.record(Duration::from_millis(ms));
+        }
+
+        let p = window.percentiles().unwrap();
+        assert!((p.p50 - 0.050).abs() < 1e-9);
+        assert!((p.p95 - 0.095).abs() < 1e-9);
+        assert!((p.p99 - 0.099).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_tracked_stream() {
+        let tracker = MetricsTracker::new();
+
+        let inner: InferenceStream = Box::pin(stream::iter(vec![
+            chunk("Hello", false),
+            chunk(" world", false),
+            chunk("", true),
+        ]));
+        let chunks: Vec<_> = tracker.start().track_stream(inner).collect().await;
+        assert_eq!(chunks.len(), 3);
+
+        let inner: InferenceStream = Box::pin(stream::iter(vec![
+            chunk("Hi", false),
+            Err(AxonError::InferenceFailed("engine died".into())),
+        ]));
+        let _: Vec<_> = tracker.start().track_stream(inner).collect().await;
+
+        let metrics = tracker.snapshot();
+        assert_eq!(metrics.pending_requests, 0);
+        assert_eq!(metrics.total_requests, 2);
+        assert_eq!(metrics.failed_requests, 1);
+        assert!(metrics.time_to_first_token.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_run_stream_records_failure_to_open() {
+        let tracker = MetricsTracker::new();
+
+        let result = tracker.start().run_stream(async { Err(AxonError::BackendNotRunning) }).await;
+        assert!(result.is_err());
+
+        let opened = async { Ok(Box::pin(stream::iter(vec![chunk("Hi", true)])) as InferenceStream) };
+        let chunks: Vec<_> = tracker.start().run_stream(opened).await.unwrap().collect().await;
+        assert_eq!(chunks.len(), 1);
+
+        let metrics = tracker.snapshot();
+        assert_eq!(metrics.pending_requests, 0);
+        assert_eq!(metrics.total_requests, 2);
+        assert_eq!(metrics.failed_requests, 1);
+    }
+}
@@ -0,0 +1,380 @@
+//! OpenAI-compatible chat wire format
+//!
+//! vLLM and TGI both expose `/v1/chat/completions` following the OpenAI
+//! schema. The message and response types here are shared by their clients;
+//! sampling fields stay with each backend because the engines accept
+//! different extensions.
+
+use crate::types::{
+    ChatMessage, ContentPart, MessageContent, TokenLogprob, Tool, ToolCall, ToolChoice, TopLogprob, Usage,
+};
+use serde::{Deserialize, Serialize};
+
+/// A chat message as sent on the wire
+#[derive(Debug, Serialize)]
+pub(crate) struct OpenAiMessage {
+    role: &'static str,
+    /// Null for an assistant message that only makes tool calls
+    content: Option<OpenAiContent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_call_id: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tool_calls: Vec<OpenAiToolCall>,
+}
+
+impl From<&ChatMessage> for OpenAiMessage {
+    fn from(message: &ChatMessage) -> Self {
+        let content = match &message.content {
+            MessageContent::Text(text) => OpenAiContent::Text(text.clone()),
+            MessageContent::Parts(parts) => OpenAiContent::Parts(
+                parts.iter().map(|part| match part {
+                    ContentPart::Text(text) => OpenAiContentPart::Text { text: text.clone() },
+                    ContentPart::ImageUrl(url) => OpenAiContentPart::ImageUrl {
+                        image_url: OpenAiImageUrl { url: url.clone() },
+                    },
+                }).collect(),
+            ),
+        };
+
+        let tool_calls_only = !message.tool_calls.is_empty() && message.content == MessageContent::Text(String::new());
+
+        Self {
+            role: message.role.as_str(),
+            content: (!tool_calls_only).then_some(content),
+            name: message.name.clone(),
+            tool_call_id: message.tool_call_id.clone(),
+            tool_calls: message.tool_calls.iter().map(OpenAiToolCall::from).collect(),
+        }
+    }
+}
+
+/// A tool call in an assistant message, sent in history or received
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct OpenAiToolCall {
+    id: String,
+    #[serde(rename = "type", default = "function_type")]
+    kind: String,
+    function: OpenAiFunctionCall,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct OpenAiFunctionCall {
+    name: String,
+    #[serde(default)]
+    arguments: String,
+}
+
+fn function_type() -> String {
+    "function".to_string()
+}
+
+impl From<&ToolCall> for OpenAiToolCall {
+    fn from(call: &ToolCall) -> Self {
+        Self {
+            id: call.id.clone(),
+            kind: function_type(),
+            function: OpenAiFunctionCall {
+                name: call.name.clone(),
+                arguments: call.arguments.clone(),
+            },
+        }
+    }
+}
+
+impl From<OpenAiToolCall> for ToolCall {
+    fn from(call: OpenAiToolCall) -> Self {
+        Self {
+            id: call.id,
+            name: call.function.name,
+            arguments: call.function.arguments,
+        }
+    }
+}
+
+/// A function offered to the model
+#[derive(Debug, Serialize)]
+pub(crate) struct OpenAiTool {
+    #[serde(rename = "type")]
+    kind: &'static str,
+    function: OpenAiFunction,
+}
+
+#[derive(Debug, Serialize)]
+struct OpenAiFunction {
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    parameters: serde_json::Value,
+}
+
+impl From<&Tool> for OpenAiTool {
+    fn from(tool: &Tool) -> Self {
+        Self {
+            kind: "function",
+            function: OpenAiFunction {
+                name: tool.name.clone(),
+                description: tool.description.clone(),
+                parameters: tool.parameters.clone(),
+            },
+        }
+    }
+}
+
+/// `tool_choice`: a mode name, or the function the model must call
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub(crate) enum OpenAiToolChoice {
+    Mode(&'static str),
+    Function {
+        #[serde(rename = "type")]
+        kind: &'static str,
+        function: OpenAiFunctionName,
+    },
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct OpenAiFunctionName {
+    name: String,
+}
+
+impl From<&ToolChoice> for OpenAiToolChoice {
+    fn from(choice: &ToolChoice) -> Self {
+        match choice {
+            ToolChoice::Auto => Self::Mode("auto"),
+            ToolChoice::None => Self::Mode("none"),
+            ToolChoice::Required => Self::Mode("required"),
+            ToolChoice::Function(name) => Self::Function {
+                kind: "function",
+                function: OpenAiFunctionName { name: name.clone() },
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum OpenAiContent {
+    Text(String),
+    Parts(Vec<OpenAiContentPart>),
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum OpenAiContentPart {
+    Text { text: String },
+    ImageUrl { image_url: OpenAiImageUrl },
+}
+
+#[derive(Debug, Serialize)]
+struct OpenAiImageUrl {
+    url: String,
+}
+
+/// Chat completion response format
+#[derive(Debug, Deserialize)]
+pub(crate) struct OpenAiChatResponse {
+    pub(crate) choices: Vec<OpenAiChatChoice>,
+    pub(crate) usage: Option<OpenAiUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct OpenAiChatChoice {
+    pub(crate) message: OpenAiResponseMessage,
+    pub(crate) finish_reason: Option<String>,
+    pub(crate) logprobs: Option<OpenAiLogprobs>,
+    /// vLLM extension: IDs of the generated tokens, if `return_token_ids` was set
+    pub(crate) token_ids: Option<Vec<u32>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct OpenAiResponseMessage {
+    pub(crate) content: Option<String>,
+    /// Null or absent when the model called no tools
+    #[serde(default)]
+    tool_calls: Option<Vec<OpenAiToolCall>>,
+}
+
+impl OpenAiResponseMessage {
+    /// Tools the model called, in Axon's format
+    pub(crate) fn take_tool_calls(&mut self) -> Vec<ToolCall> {
+        self.tool_calls.take().unwrap_or_default().into_iter().map(ToolCall::from).collect()
+    }
+}
+
+/// Log probabilities of a chat choice's content
+#[derive(Debug, Deserialize)]
+pub(crate) struct OpenAiLogprobs {
+    content: Option<Vec<OpenAiTokenLogprob>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiTokenLogprob {
+    token: String,
+    logprob: f32,
+    #[serde(default)]
+    top_logprobs: Vec<OpenAiTopLogprob>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OpenAiTopLogprob {
+    token: String,
+    logprob: f32,
+}
+
+impl OpenAiLogprobs {
+    /// Convert to Axon's format, pairing tokens with `token_ids` if known
+    pub(crate) fn into_tokens(self, token_ids: Option<&[u32]>) -> Vec<TokenLogprob> {
+        self.content.unwrap_or_default().into_iter().enumerate().map(|(i, entry)| TokenLogprob {
+            token: entry.token,
+            token_id: token_ids.and_then(|ids| ids.get(i).copied()),
+            logprob: entry.logprob,
+            top_logprobs: entry.top_logprobs.into_iter().map(|top| TopLogprob {
+                token: top.token,
+                token_id: None,
+                logprob: top.logprob,
+            }).collect(),
+        }).collect()
+    }
+}
+
+/// Token usage block of completion and chat responses
+#[derive(Debug, Deserialize)]
+pub(crate) struct OpenAiUsage {
+    pub(crate) prompt_tokens: usize,
+    #[serde(default)]
+    pub(crate) completion_tokens: usize,
+    pub(crate) prompt_tokens_details: Option<OpenAiPromptTokensDetails>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct OpenAiPromptTokensDetails {
+    pub(crate) cached_tokens: Option<usize>,
+}
+
+impl From<OpenAiUsage> for Usage {
+    fn from(usage: OpenAiUsage) -> Self {
+        Self {
+            prompt_tokens: usage.prompt_tokens,
+            completion_tokens: usage.completion_tokens,
+            cached_prompt_tokens: usage.prompt_tokens_details.and_then(|d| d.cached_tokens),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_message_serialization() {
+        let message = ChatMessage::user(MessageContent::Parts(vec![
+            ContentPart::Text("What is this?".to_string()),
+            ContentPart::ImageUrl("https://example.com/cat.png".to_string()),
+        ]));
+
+        let json = serde_json::to_value(OpenAiMessage::from(&message)).unwrap();
+        assert_eq!(json["role"], "user");
+        assert_eq!(json["content"][0]["type"], "text");
+        assert_eq!(json["content"][1]["image_url"]["url"], "https://example.com/cat.png");
+        assert!(json.get("tool_call_id").is_none());
+        assert!(json.get("tool_calls").is_none());
+    }
+
+    #[test]
+    fn test_tool_call_round_trip() {
+        let call = ToolCall {
+            id: "call-1".to_string(),
+            name: "get_weather".to_string(),
+            arguments: r#"{"city":"Paris"}"#.to_string(),
+        };
+
+        let json = serde_json::to_value(OpenAiMessage::from(&ChatMessage::assistant_tool_calls(vec![call]))).unwrap();
+        assert_eq!(json["role"], "assistant");
+        assert!(json["content"].is_null());
+        assert_eq!(json["tool_calls"][0]["id"], "call-1");
+        assert_eq!(json["tool_calls"][0]["type"], "function");
+        assert_eq!(json["tool_calls"][0]["function"]["name"], "get_weather");
+        assert_eq!(json["tool_calls"][0]["function"]["arguments"], r#"{"city":"Paris"}"#);
+
+        let answer = serde_json::to_value(OpenAiMessage::from(&ChatMessage::tool("call-1", "18C"))).unwrap();
+        assert_eq!(answer["tool_call_id"], "call-1");
+        assert_eq!(answer["content"], "18C");
+    }
+
+    #[test]
+    fn test_chat_response_deserialization() {
+        let resp: OpenAiChatResponse = serde_json::from_str(
+            r#"{"id":"chat-1","choices":[{"index":0,"message":{"role":"assistant","content":"Hi"},"finish_reason":"stop"}],"usage":{"prompt_tokens":5,"completion_tokens":1,"total_tokens":6}}"#,
+        )
+        .unwrap();
+        assert_eq!(resp.choices[0].message.content.as_deref(), Some("Hi"));
+        assert_eq!(resp.usage.unwrap().completion_tokens, 1);
+    }
+
+    #[test]
+    fn test_tool_call_response() {
+        let resp: OpenAiChatResponse = serde_json::from_str(
+            r#"{"choices":[{"index":0,"message":{"role":"assistant","content":null,"tool_calls":[{"id":"chatcmpl-tool-1","type":"function","function":{"name":"get_weather","arguments":"{\"city\": \"Paris\"}"}}]},"finish_reason":"tool_calls"}]}"#,
+        )
+        .unwrap();
+        let mut choice = resp.choices.into_iter().next().unwrap();
+
+        assert_eq!(choice.finish_reason.as_deref(), Some("tool_calls"));
+        assert_eq!(choice.message.content, None);
+        let calls = choice.message.take_tool_calls();
+        assert_eq!(calls, [ToolCall {
+            id: "chatcmpl-tool-1".to_string(),
+            name: "get_weather".to_string(),
+            arguments: r#"{"city": "Paris"}"#.to_string(),
+        }]);
+    }
+
+    #[test]
+    fn test_tools_serialization() {
+        let tool = Tool {
+            name: "get_weather".to_string(),
+            description: None,
+            parameters: serde_json::json!({"type": "object", "properties": {"city": {"type": "string"}}}),
+        };
+        let json = serde_json::to_value(OpenAiTool::from(&tool)).unwrap();
+        assert_eq!(json["type"], "function");
+        assert_eq!(json["function"]["name"], "get_weather");
+        assert!(json["function"].get("description").is_none());
+        assert_eq!(json["function"]["parameters"]["type"], "object");
+
+        assert_eq!(serde_json::to_value(OpenAiToolChoice::from(&ToolChoice::Required)).unwrap(), "required");
+        let forced = serde_json::to_value(OpenAiToolChoice::from(&ToolChoice::Function("get_weather".into()))).unwrap();
+        assert_eq!(forced["function"]["name"], "get_weather");
+    }
+
+    #[test]
+    fn test_logprobs_conversion() {
+        let resp: OpenAiChatResponse = serde_json::from_str(
+            r#"{"choices":[{"index":0,"message":{"role":"assistant","content":"Hi"},"finish_reason":"stop","token_ids":[13347],
+                "logprobs":{"content":[{"token":"Hi","logprob":-0.1,"bytes":[72,105],"top_logprobs":[{"token":"Hi","logprob":-0.1},{"token":"Hello","logprob":-2.5}]}]}}]}"#,
+        )
+        .unwrap();
+        let choice = resp.choices.into_iter().next().unwrap();
+        let tokens = choice.logprobs.unwrap().into_tokens(choice.token_ids.as_deref());
+
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].token, "Hi");
+        assert_eq!(tokens[0].token_id, Some(13347));
+        assert_eq!(tokens[0].top_logprobs[1].token, "Hello");
+    }
+
+    #[test]
+    fn test_usage_conversion() {
+        let usage: OpenAiUsage = serde_json::from_str(
+            r#"{"prompt_tokens":120,"completion_tokens":8,"total_tokens":128,"prompt_tokens_details":{"cached_tokens":96}}"#,
+        )
+        .unwrap();
+        let usage = Usage::from(usage);
+
+        assert_eq!(usage.total_tokens(), 128);
+        assert_eq!(usage.cached_prompt_tokens, Some(96));
+        assert_eq!(usage.tokens_per_second(std::time::Duration::from_secs(2)), 4.0);
+    }
+}
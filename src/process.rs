@@ -0,0 +1,206 @@
+//! Child processes for spawned inference engines
+//!
+//! An `EngineProcess` is started as the leader of its own process group, so
+//! workers it forks (Ray or multiprocessing workers for vLLM, shards for
+//! TGI) are signalled together with it. The `tokio::process::Child` is owned
+//! by a reaper task that waits on it, so an exited engine is reaped
+//! immediately instead of lingering as a zombie, and its exit status is
+//! published to every handle. Both output pipes are drained into log sinks.
+
+use crate::backend::ProcessExit;
+use crate::error::{AxonError, Result};
+use crate::logs::{self, LogSink, LogSinks, LogStream, RingBufferSink};
+use std::io;
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::process::{Child, Command};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, timeout, timeout_at, Instant};
+
+/// A spawned engine process and its process group
+pub(crate) struct EngineProcess {
+    /// The child process ID, which is also its process group ID
+    pid: u32,
+
+    /// Exit status, published by the reaper task once the process ends
+    exit: watch::Receiver<Option<ProcessExit>>,
+
+    /// Most recent engine output, for error reports
+    output_tail: Arc<RingBufferSink>,
+}
+
+impl EngineProcess {
+    /// Start `cmd` in a new process group, draining its output into `sinks`
+    /// and keeping the last `tail_lines` lines
+    ///
+    /// `engine` names the engine in error messages.
+    pub(crate) fn start(mut cmd: Command, engine: &str, mut sinks: LogSinks, tail_lines: usize) -> Result<Self> {
+        cmd.stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .process_group(0);
+
+        // Take the engine down with us if this process dies without cleaning up
+        let parent = unsafe { libc::getpid() };
+        unsafe {
+            cmd.pre_exec(move || die_with_parent(parent));
+        }
+
+        let mut child = cmd.spawn()
+            .map_err(|e| AxonError::ModelLoadFailed(format!("Failed to spawn {}: {}", engine, e)))?;
+        let pid = child.id()
+            .ok_or_else(|| AxonError::ModelLoadFailed(format!("{} exited immediately", engine)))?;
+
+        // Drain both pipes so the engine never blocks on a full buffer
+        let output_tail = Arc::new(RingBufferSink::new(tail_lines));
+        sinks.push(output_tail.clone());
+        let sinks: Arc<dyn LogSink> = Arc::new(sinks);
+
+        let mut drains = Vec::new();
+        if let Some(stdout) = child.stdout.take() {
+            drains.push(tokio::spawn(logs::drain(stdout, LogStream::Stdout, sinks.clone())));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            drains.push(tokio::spawn(logs::drain(stderr, LogStream::Stderr, sinks)));
+        }
+
+        let (tx, exit) = watch::channel(None);
+        tokio::spawn(reap(child, drains, tx));
+
+        Ok(Self { pid, exit, output_tail })
+    }
+
+    /// Process ID, which also leads the process group
+    pub(crate) fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    /// The most recent lines the engine wrote to stdout/stderr
+    pub(crate) fn recent_output(&self) -> Vec<String> {
+        self.output_tail.lines()
+    }
+
+    /// How the process ended, or `None` while it is still running
+    pub(crate) fn exit_status(&self) -> Option<ProcessExit> {
+        *self.exit.borrow()
+    }
+
+    /// Check if the process is still running
+    pub(crate) fn is_running(&self) -> bool {
+        self.exit_status().is_none()
+    }
+
+    /// Wait for the process to exit
+    pub(crate) async fn wait(&self) -> ProcessExit {
+        let mut exit = self.exit.clone();
+        match exit.wait_for(Option::is_some).await {
+            Ok(status) => status.unwrap_or_default(),
+            // The reaper always publishes before dropping its sender
+            Err(_) => ProcessExit::default(),
+        }
+    }
+
+    /// Terminate the process group
+    ///
+    /// Sends SIGTERM, then SIGKILL if any member is still alive after
+    /// `grace`, and returns once the leader has been reaped.
+    pub(crate) async fn terminate(&self, grace: Duration) -> ProcessExit {
+        let deadline = Instant::now() + grace;
+        self.signal_group(libc::SIGTERM);
+
+        // Give the engine, then any workers it left behind, time to exit
+        let _ = timeout_at(deadline, self.wait()).await;
+        while self.group_alive() && Instant::now() < deadline {
+            sleep(Duration::from_millis(100)).await;
+        }
+
+        // Force kill whatever is still running
+        if self.is_running() || self.group_alive() {
+            self.signal_group(libc::SIGKILL);
+        }
+        self.wait().await
+    }
+
+    /// Send a signal to every process in the group
+    ///
+    /// The kernel does not reuse a PID while a process group with that ID
+    /// exists, so this is safe even after the leader has been reaped.
+    fn signal_group(&self, signal: i32) {
+        unsafe {
+            libc::kill(-(self.pid as i32), signal);
+        }
+    }
+
+    /// Whether any process in the group is still alive
+    fn group_alive(&self) -> bool {
+        unsafe { libc::kill(-(self.pid as i32), 0) == 0 }
+    }
+}
+
+impl Drop for EngineProcess {
+    fn drop(&mut self) {
+        // Last resort when dropped without `terminate`, e.g. when a runtime
+        // shuts down mid-task; there is no chance for a graceful SIGTERM
+        if self.is_running() {
+            self.signal_group(libc::SIGKILL);
+        }
+    }
+}
+
+/// Runs in the forked child before exec: ask the kernel to SIGTERM the
+/// engine when `parent` exits
+///
+/// The death signal fires when the forking thread exits, which for Tokio
+/// worker threads is when the runtime shuts down.
+fn die_with_parent(parent: libc::pid_t) -> io::Result<()> {
+    #[cfg(target_os = "linux")]
+    unsafe {
+        if libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGTERM) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+
+    // The parent may have died before the death signal was armed
+    if unsafe { libc::getppid() } != parent {
+        return Err(io::Error::other("parent exited before the engine started"));
+    }
+    Ok(())
+}
+
+/// How long the reaper waits for the last output after the engine exits
+const DRAIN_GRACE: Duration = Duration::from_millis(200);
+
+/// Wait on the child and publish its exit status
+///
+/// The final output usually explains a crash, so it is drained first. The
+/// wait is bounded because workers that outlive the engine keep the pipes
+/// open.
+async fn reap(mut child: Child, drains: Vec<JoinHandle<()>>, tx: watch::Sender<Option<ProcessExit>>) {
+    let exit = match child.wait().await {
+        Ok(status) => ProcessExit::from(status),
+        Err(_) => ProcessExit::default(),
+    };
+    let _ = timeout(DRAIN_GRACE, futures_util::future::join_all(drains)).await;
+    let _ = tx.send(Some(exit));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_exited_process_is_reaped() {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg("echo 'shard 0 failed' >&2; exit 1");
+        let process = EngineProcess::start(cmd, "test", LogSinks::new(), 10).unwrap();
+
+        assert_eq!(process.wait().await.code, Some(1));
+        assert!(!process.is_running());
+
+        // No zombie is left behind for the PID
+        let stat = std::fs::read_to_string(format!("/proc/{}/stat", process.pid())).unwrap_or_default();
+        assert!(!stat.contains(") Z "), "zombie: {}", stat);
+    }
+}
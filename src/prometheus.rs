@@ -0,0 +1,147 @@
+//! Prometheus text exposition format parsing
+//!
+//! Just enough of the format to read gauges and counters scraped from an
+//! engine's `/metrics` endpoint: `# HELP`/`# TYPE` lines are skipped and
+//! timestamps are ignored.
+
+/// A single sample line
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Sample {
+    /// Metric name, including any `_total`/`_bucket` suffix
+    pub(crate) name: String,
+
+    /// Label pairs in source order
+    pub(crate) labels: Vec<(String, String)>,
+
+    /// Sample value
+    pub(crate) value: f64,
+}
+
+/// Parse a scrape body, skipping lines that are not valid samples
+pub(crate) fn parse(text: &str) -> Vec<Sample> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(parse_line)
+        .collect()
+}
+
+/// Sum the values of every series with the given name
+pub(crate) fn sum(samples: &[Sample], name: &str) -> Option<f64> {
+    samples.iter()
+        .filter(|s| s.name == name)
+        .map(|s| s.value)
+        .reduce(|a, b| a + b)
+}
+
+/// Largest value across every series with the given name
+pub(crate) fn max(samples: &[Sample], name: &str) -> Option<f64> {
+    samples.iter()
+        .filter(|s| s.name == name)
+        .map(|s| s.value)
+        .reduce(f64::max)
+}
+
+fn parse_line(line: &str) -> Option<Sample> {
+    let (name, labels, rest) = match line.find('{') {
+        Some(open) => {
+            let close = find_label_end(line, open)?;
+            (&line[..open], parse_labels(&line[open + 1..close])?, &line[close + 1..])
+        }
+        None => {
+            let split = line.find(char::is_whitespace)?;
+            (&line[..split], Vec::new(), &line[split..])
+        }
+    };
+
+    let value = rest.split_whitespace().next()?;
+    let value = match value {
+        "+Inf" => f64::INFINITY,
+        "-Inf" => f64::NEG_INFINITY,
+        v => v.parse().ok()?,
+    };
+
+    Some(Sample {
+        name: name.trim().to_string(),
+        labels,
+        value,
+    })
+}
+
+/// Find the closing brace of a label set, skipping braces inside quoted values
+fn find_label_end(line: &str, open: usize) -> Option<usize> {
+    let mut in_quotes = false;
+    let mut escaped = false;
+
+    for (i, c) in line[open + 1..].char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_quotes => escaped = true,
+            '"' => in_quotes = !in_quotes,
+            '}' if !in_quotes => return Some(open + 1 + i),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn parse_labels(body: &str) -> Option<Vec<(String, String)>> {
+    let mut labels = Vec::new();
+    let mut rest = body.trim();
+
+    while !rest.is_empty() {
+        let eq = rest.find('=')?;
+        let key = rest[..eq].trim().to_string();
+        rest = rest[eq + 1..].trim_start().strip_prefix('"')?;
+
+        let mut value = String::new();
+        let mut chars = rest.char_indices();
+        let end = loop {
+            match chars.next()? {
+                (i, '"') => break i,
+                (_, '\\') => match chars.next()?.1 {
+                    'n' => value.push('\n'),
+                    other => value.push(other),
+                },
+                (_, c) => value.push(c),
+            }
+        };
+
+        labels.push((key, value));
+        rest = rest[end + 1..].trim_start();
+        rest = rest.strip_prefix(',').unwrap_or(rest).trim_start();
+    }
+
+    Some(labels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_samples() {
+        let text = r#"
+# HELP vllm:num_requests_running Number of requests currently running.
+# TYPE vllm:num_requests_running gauge
+vllm:num_requests_running{model_name="llama"} 3.0
+vllm:num_requests_running{model_name="mistral"} 2.0
+process_start_time_seconds 1.7e9
+weird{path="a}b",msg="say \"hi\""} 1 1700000000000
+"#;
+        let samples = parse(text);
+
+        assert_eq!(samples.len(), 4);
+        assert_eq!(samples[0].labels, vec![("model_name".to_string(), "llama".to_string())]);
+        assert_eq!(sum(&samples, "vllm:num_requests_running"), Some(5.0));
+        assert_eq!(max(&samples, "process_start_time_seconds"), Some(1.7e9));
+        assert_eq!(samples[3].labels[0].1, "a}b");
+        assert_eq!(samples[3].labels[1].1, "say \"hi\"");
+    }
+
+    #[test]
+    fn test_missing_metric() {
+        let samples = parse("up 1\n");
+        assert_eq!(sum(&samples, "down"), None);
+    }
+}
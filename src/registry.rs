@@ -0,0 +1,185 @@
+//! Backend registry
+//!
+//! Builds type-erased backends from configuration strings, so the inference
+//! engine can be chosen in a config file instead of in code.
+
+use crate::backend::DynBackend;
+use crate::error::{AxonError, Result};
+use crate::tgi::TgiBackend;
+use crate::triton::TritonBackend;
+use crate::vllm::VllmBackend;
+use std::collections::HashMap;
+
+/// Constructor for a backend kind
+///
+/// Receives the endpoint part of the spec (after the first `:`), if any.
+pub type BackendFactory = Box<dyn Fn(Option<&str>) -> Result<DynBackend> + Send + Sync>;
+
+/// Registry mapping backend kinds to factories
+///
+/// A backend spec is a kind name, optionally followed by `:` and an
+/// endpoint URL:
+///
+/// | Spec | Backend |
+/// |------|---------|
+/// | `vllm` | vLLM spawned by Axon |
+/// | `vllm-remote:http://host:8000` | existing vLLM server |
+/// | `tgi` | TGI launcher spawned by Axon |
+/// | `tgi-remote:http://host:8080` | existing TGI server |
+/// | `triton:http://host:8000` | existing Triton server |
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axon::{BackendRegistry, InferenceBackend, ModelConfig};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let spec = std::env::var("AXON_BACKEND").unwrap_or_else(|_| "vllm".to_string());
+/// let mut backend = BackendRegistry::with_defaults().create(&spec)?;
+///
+/// backend.load_model(ModelConfig {
+///     model_name: "meta-llama/Llama-2-7b-hf".to_string(),
+///     ..Default::default()
+/// }).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct BackendRegistry {
+    factories: HashMap<String, BackendFactory>,
+}
+
+impl BackendRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self {
+            factories: HashMap::new(),
+        }
+    }
+
+    /// Create a registry with all built-in backends registered
+    pub fn with_defaults() -> Self {
+        let mut registry = Self::new();
+
+        registry.register("vllm", |endpoint| {
+            no_endpoint("vllm", endpoint)?;
+            Ok(DynBackend::new(VllmBackend::new()))
+        });
+        registry.register("vllm-remote", |endpoint| {
+            let url = require_endpoint("vllm-remote", endpoint)?;
+            Ok(DynBackend::new(VllmBackend::connect_to(url)))
+        });
+        registry.register("tgi", |endpoint| {
+            no_endpoint("tgi", endpoint)?;
+            Ok(DynBackend::new(TgiBackend::new()))
+        });
+        registry.register("tgi-remote", |endpoint| {
+            let url = require_endpoint("tgi-remote", endpoint)?;
+            Ok(DynBackend::new(TgiBackend::connect_to(url)))
+        });
+        registry.register("triton", |endpoint| {
+            let url = require_endpoint("triton", endpoint)?;
+            Ok(DynBackend::new(TritonBackend::connect_to(url)))
+        });
+
+        registry
+    }
+
+    /// Register a factory for a backend kind, replacing any existing one
+    pub fn register<F>(&mut self, kind: impl Into<String>, factory: F)
+    where
+        F: Fn(Option<&str>) -> Result<DynBackend> + Send + Sync + 'static,
+    {
+        self.factories.insert(kind.into(), Box::new(factory));
+    }
+
+    /// Registered backend kinds, sorted
+    pub fn kinds(&self) -> Vec<&str> {
+        let mut kinds: Vec<&str> = self.factories.keys().map(String::as_str).collect();
+        kinds.sort_unstable();
+        kinds
+    }
+
+    /// Build a backend from a spec such as `vllm` or `tgi-remote:http://host:8080`
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidConfig` if the kind is unknown or the factory rejects
+    /// the endpoint.
+    pub fn create(&self, spec: &str) -> Result<DynBackend> {
+        let spec = spec.trim();
+        let (kind, endpoint) = match spec.split_once(':') {
+            Some((kind, endpoint)) => (kind, Some(endpoint)),
+            None => (spec, None),
+        };
+
+        let factory = self.factories.get(kind).ok_or_else(|| {
+            AxonError::InvalidConfig(format!(
+                "unknown backend '{}' (available: {})",
+                kind,
+                self.kinds().join(", ")
+            ))
+        })?;
+
+        factory(endpoint)
+    }
+}
+
+impl Default for BackendRegistry {
+    fn default() -> Self {
+        Self::with_defaults()
+    }
+}
+
+/// Reject an endpoint for kinds that spawn their own server
+fn no_endpoint(kind: &str, endpoint: Option<&str>) -> Result<()> {
+    match endpoint {
+        Some(_) => Err(AxonError::InvalidConfig(format!(
+            "backend '{}' spawns its own server; use '{}-remote:<url>' to connect to one",
+            kind, kind
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Require a non-empty endpoint URL
+fn require_endpoint(kind: &str, endpoint: Option<&str>) -> Result<String> {
+    match endpoint {
+        Some(url) if !url.is_empty() => Ok(url.to_string()),
+        _ => Err(AxonError::InvalidConfig(format!(
+            "backend '{}' needs an endpoint, e.g. '{}:http://localhost:8000'",
+            kind, kind
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::{HealthStatus, InferenceBackend};
+
+    #[test]
+    fn test_default_kinds() {
+        let registry = BackendRegistry::with_defaults();
+        assert_eq!(registry.kinds(), vec!["tgi", "tgi-remote", "triton", "vllm", "vllm-remote"]);
+    }
+
+    #[tokio::test]
+    async fn test_create_from_spec() {
+        let registry = BackendRegistry::default();
+
+        let backend = registry.create("vllm").unwrap();
+        assert_eq!(backend.health_check().await, HealthStatus::Starting);
+
+        assert!(registry.create("vllm-remote:http://localhost:8000").is_ok());
+    }
+
+    #[test]
+    fn test_create_errors() {
+        let registry = BackendRegistry::with_defaults();
+
+        assert!(matches!(registry.create("llamacpp"), Err(AxonError::InvalidConfig(_))));
+        assert!(matches!(registry.create("vllm-remote"), Err(AxonError::InvalidConfig(_))));
+        assert!(matches!(registry.create("vllm:http://x"), Err(AxonError::InvalidConfig(_))));
+    }
+}
@@ -0,0 +1,222 @@
+//! Retries for transient request failures
+//!
+//! A `RetryPolicy` decides whether and when a failed request is sent again.
+//! Only errors that `AxonError::is_retryable` classifies as transient are
+//! retried. A retry budget shared by all requests of a backend keeps retries
+//! from multiplying the load on an engine that is failing outright.
+
+use crate::error::{AxonError, Result};
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::sleep;
+
+/// How failed requests are retried
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Total attempts per request, including the first; 1 disables retries
+    pub max_attempts: u32,
+
+    /// Delay before the first retry; doubles with each further retry
+    pub initial_backoff: Duration,
+
+    /// Upper bound on the delay between attempts
+    pub max_backoff: Duration,
+
+    /// Fraction of each delay that is randomized, from 0.0 to 1.0
+    ///
+    /// Spreads out retries from requests that failed at the same moment,
+    /// e.g. during an engine restart.
+    pub jitter: f64,
+
+    /// Retries earned per request, e.g. 0.2 allows one retry per five requests
+    pub budget_ratio: f64,
+
+    /// Retries available up front and the most the budget can accumulate
+    pub budget_burst: u32,
+
+    /// Wait for the delay a 429 or 503 response asks for in `Retry-After`
+    ///
+    /// If the requested delay exceeds `max_backoff` the request is not
+    /// retried.
+    pub honor_retry_after: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(5),
+            jitter: 0.5,
+            budget_ratio: 0.2,
+            budget_burst: 10,
+            honor_retry_after: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// A policy that never retries
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Default::default()
+        }
+    }
+
+    /// Delay before retry number `retry` (counting from 1), with jitter applied
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry.saturating_sub(1));
+        let delay = self.initial_backoff.saturating_mul(factor).min(self.max_backoff);
+        delay.mul_f64(1.0 - self.jitter.clamp(0.0, 1.0) * fastrand::f64())
+    }
+}
+
+/// Runs requests under a `RetryPolicy`, tracking the shared retry budget
+#[derive(Debug)]
+pub(crate) struct Retrier {
+    policy: RetryPolicy,
+
+    /// Retries currently available
+    budget: Mutex<f64>,
+}
+
+impl Retrier {
+    pub(crate) fn new(policy: RetryPolicy) -> Self {
+        let budget = Mutex::new(policy.budget_burst as f64);
+        Self { policy, budget }
+    }
+
+    /// Run `op` until it succeeds, fails permanently, or runs out of
+    /// attempts or budget, returning the result and the attempts made
+    pub(crate) async fn run<T, F, Fut>(&self, mut op: F) -> Result<(T, u32)>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        {
+            let mut budget = self.budget.lock().unwrap();
+            *budget = (*budget + self.policy.budget_ratio).min(self.policy.budget_burst as f64);
+        }
+
+        let mut attempt = 1;
+        loop {
+            let err = match op().await {
+                Ok(value) => return Ok((value, attempt)),
+                Err(e) => e,
+            };
+            let Some(delay) = self.retry_delay(&err, attempt) else {
+                return Err(err);
+            };
+
+            tracing::debug!(attempt, ?delay, error = %err, "retrying request");
+            sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    /// How long to wait before retrying after `err`, or `None` to give up
+    fn retry_delay(&self, err: &AxonError, attempt: u32) -> Option<Duration> {
+        if attempt >= self.policy.max_attempts || !err.is_retryable() {
+            return None;
+        }
+
+        let mut delay = self.policy.backoff(attempt);
+        if self.policy.honor_retry_after
+            && let AxonError::Api(api) = err
+            && let Some(after) = api.retry_after
+        {
+            if after > self.policy.max_backoff {
+                return None;
+            }
+            delay = delay.max(after);
+        }
+
+        // Withdraw from the budget last, so only retries actually made count
+        let mut budget = self.budget.lock().unwrap();
+        if *budget < 1.0 {
+            return None;
+        }
+        *budget -= 1.0;
+        Some(delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ApiError;
+    use std::future::ready;
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(10),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_backoff_grows_and_is_capped() {
+        let policy = RetryPolicy {
+            jitter: 0.0,
+            ..Default::default()
+        };
+        assert_eq!(policy.backoff(1), Duration::from_millis(250));
+        assert_eq!(policy.backoff(3), Duration::from_secs(1));
+        assert_eq!(policy.backoff(10), Duration::from_secs(5));
+
+        let jittered = RetryPolicy::default().backoff(2);
+        assert!(jittered > Duration::from_millis(250) && jittered <= Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn test_retries_transient_errors_only() {
+        let retrier = Retrier::new(policy());
+
+        let mut calls = 0;
+        let (value, attempts) = retrier.run(|| {
+            calls += 1;
+            ready(if calls < 3 {
+                Err(ApiError::from_response(503, "loading").into())
+            } else {
+                Ok(calls)
+            })
+        }).await.unwrap();
+        assert_eq!((value, attempts), (3, 3));
+
+        let mut calls = 0;
+        let result = retrier.run(|| {
+            calls += 1;
+            ready(Err::<(), _>(ApiError::from_response(400, "bad request").into()))
+        }).await;
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    #[tokio::test]
+    async fn test_budget_and_retry_after_limit_retries() {
+        let retrier = Retrier::new(RetryPolicy {
+            budget_burst: 1,
+            budget_ratio: 0.0,
+            ..policy()
+        });
+
+        let mut calls = 0;
+        let _ = retrier.run(|| {
+            calls += 1;
+            ready(Err::<(), _>(AxonError::BackendNotRunning))
+        }).await;
+        assert_eq!(calls, 2, "one retry allowed by the budget");
+
+        let retrier = Retrier::new(policy());
+        let mut calls = 0;
+        let _ = retrier.run(|| {
+            calls += 1;
+            let mut err = ApiError::from_response(429, "slow down");
+            err.retry_after = Some(Duration::from_secs(30));
+            ready(Err::<(), _>(err.into()))
+        }).await;
+        assert_eq!(calls, 1, "Retry-After beyond max_backoff is not waited out");
+    }
+}
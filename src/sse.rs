@@ -0,0 +1,155 @@
+//! Server-sent events decoding
+//!
+//! Inference engines stream generated tokens as `text/event-stream`
+//! responses. This module turns a raw HTTP body into the `data:` payload
+//! of each event.
+
+use crate::error::{AxonError, Result};
+use futures_util::stream::{self, Stream, StreamExt};
+use std::collections::VecDeque;
+
+/// Incremental decoder for the SSE wire format
+#[derive(Debug, Default)]
+pub(crate) struct SseDecoder {
+    /// Bytes received but not yet terminated by a newline
+    buffer: Vec<u8>,
+
+    /// Data lines of the event currently being assembled
+    data: Option<String>,
+}
+
+impl SseDecoder {
+    /// Create an empty decoder
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed raw bytes, returning the payloads of every event they complete
+    pub(crate) fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(bytes);
+
+        let mut events = Vec::new();
+        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+
+            if let Some(event) = self.process_line(line) {
+                events.push(event);
+            }
+        }
+        events
+    }
+
+    /// Flush the final event if the stream ended without a blank line
+    pub(crate) fn finish(&mut self) -> Option<String> {
+        if !self.buffer.is_empty() {
+            let line = String::from_utf8_lossy(&self.buffer).into_owned();
+            self.buffer.clear();
+            self.process_line(line.trim_end_matches('\r'));
+        }
+        self.data.take()
+    }
+
+    /// Handle a single line, returning a payload when an event is dispatched
+    fn process_line(&mut self, line: &str) -> Option<String> {
+        if line.is_empty() {
+            return self.data.take();
+        }
+
+        // Lines starting with a colon are comments (often keep-alives)
+        if line.starts_with(':') {
+            return None;
+        }
+
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+
+        // Only `data` carries payload; `event`, `id` and `retry` are unused
+        if field == "data" {
+            match &mut self.data {
+                Some(data) => {
+                    data.push('\n');
+                    data.push_str(value);
+                }
+                None => self.data = Some(value.to_string()),
+            }
+        }
+        None
+    }
+}
+
+/// Decode a streamed HTTP body into SSE event payloads
+pub(crate) fn events<S, B>(body: S) -> impl Stream<Item = Result<String>> + Send
+where
+    S: Stream<Item = reqwest::Result<B>> + Send + Unpin,
+    B: AsRef<[u8]>,
+{
+    let state = (body, SseDecoder::new(), VecDeque::new(), false);
+
+    stream::unfold(state, |(mut body, mut decoder, mut pending, mut done)| async move {
+        loop {
+            if let Some(event) = pending.pop_front() {
+                return Some((Ok(event), (body, decoder, pending, done)));
+            }
+            if done {
+                return None;
+            }
+
+            match body.next().await {
+                Some(Ok(bytes)) => pending.extend(decoder.push(bytes.as_ref())),
+                Some(Err(err)) => {
+                    done = true;
+                    return Some((Err(AxonError::from(err)), (body, decoder, pending, done)));
+                }
+                None => {
+                    done = true;
+                    pending.extend(decoder.finish());
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decoder_split_across_chunks() {
+        let mut decoder = SseDecoder::new();
+        assert!(decoder.push(b"data: {\"a\"").is_empty());
+        assert_eq!(decoder.push(b":1}\r\n\r\ndata: [DONE]\n\n"), vec!["{\"a\":1}", "[DONE]"]);
+    }
+
+    #[test]
+    fn test_decoder_comments_and_multiline() {
+        let mut decoder = SseDecoder::new();
+        let events = decoder.push(b": keep-alive\n\nevent: message\ndata: one\ndata: two\n\n");
+        assert_eq!(events, vec!["one\ntwo"]);
+    }
+
+    #[test]
+    fn test_decoder_finish_without_blank_line() {
+        let mut decoder = SseDecoder::new();
+        assert!(decoder.push(b"data: tail").is_empty());
+        assert_eq!(decoder.finish(), Some("tail".to_string()));
+        assert_eq!(decoder.finish(), None);
+    }
+
+    #[tokio::test]
+    async fn test_events_stream() {
+        let body = stream::iter(vec![
+            Ok::<_, reqwest::Error>("data: a\n\nda".as_bytes()),
+            Ok("ta: b\n\n".as_bytes()),
+        ]);
+
+        let events: Vec<String> = events(body)
+            .map(|e| e.unwrap())
+            .collect()
+            .await;
+        assert_eq!(events, vec!["a", "b"]);
+    }
+}
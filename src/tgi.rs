@@ -0,0 +1,225 @@
+//! Text Generation Inference (TGI) backend implementation
+//!
+//! This module provides an Axon backend for HuggingFace's Text Generation
+//! Inference server, launched through `text-generation-launcher`.
+
+pub mod process;
+pub mod client;
+pub mod config;
+
+use crate::backend::{BackendMetrics, HealthStatus, InferenceBackend, InferenceStream};
+use crate::error::{AxonError, Result};
+use crate::deadline::RequestLimits;
+use crate::metrics::MetricsTracker;
+use crate::types::{ChatRequest, InferenceRequest, InferenceResponse, ModelConfig};
+use std::sync::Arc;
+
+use process::TgiProcess;
+use client::TgiClient;
+use config::TgiConfig;
+
+/// TGI backend for Axon
+///
+/// Spawns and manages a TGI launcher process, communicating via its native
+/// `/generate` API and its OpenAI-compatible Messages API for chat.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axon::{InferenceBackend, tgi::TgiBackend, ModelConfig, InferenceRequest};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut backend = TgiBackend::new();
+///
+/// backend.load_model(ModelConfig {
+///     model_name: "mistralai/Mistral-7B-Instruct-v0.2".to_string(),
+///     port: Some(3000),
+///     ..Default::default()
+/// }).await?;
+///
+/// let response = backend.infer(InferenceRequest {
+///     prompt: "Explain Rust in one sentence.".to_string(),
+///     ..Default::default()
+/// }).await?;
+///
+/// println!("{}", response.text);
+/// # Ok(())
+/// # }
+/// ```
+pub struct TgiBackend {
+    /// The TGI launcher process (if spawned by Axon)
+    process: Option<TgiProcess>,
+
+    /// HTTP client for communicating with the TGI API
+    client: Option<TgiClient>,
+
+    /// Whether this backend spawned its own TGI process
+    owns_process: bool,
+
+    /// Current model configuration
+    current_model: Option<String>,
+
+    /// Metrics tracker, shared with in-flight streams
+    metrics: Arc<MetricsTracker>,
+}
+
+impl TgiBackend {
+    /// Create a new TGI backend that will spawn its own launcher
+    pub fn new() -> Self {
+        Self {
+            process: None,
+            client: None,
+            owns_process: true,
+            current_model: None,
+            metrics: MetricsTracker::new(),
+        }
+    }
+
+    /// Create a TGI backend that connects to an existing TGI server
+    ///
+    /// Use this when TGI is already running (e.g., the official Docker image).
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - The base URL of the running TGI server (e.g., "http://localhost:8080")
+    pub fn connect_to(base_url: String) -> Self {
+        Self {
+            process: None,
+            client: Some(TgiClient::new(base_url)),
+            owns_process: false,
+            current_model: None,
+            metrics: MetricsTracker::new(),
+        }
+    }
+
+    /// Get the HTTP client, failing if the server is not available
+    fn ready_client(&self) -> Result<&TgiClient> {
+        let client = self.client.as_ref()
+            .ok_or(AxonError::BackendNotRunning)?;
+
+        // Check process health if we own it
+        if self.owns_process && !self.process.as_ref().is_some_and(|p| p.is_running()) {
+            return Err(AxonError::BackendNotRunning);
+        }
+
+        Ok(client)
+    }
+}
+
+impl Default for TgiBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InferenceBackend for TgiBackend {
+    async fn load_model(&mut self, config: ModelConfig) -> Result<()> {
+        // Validate configuration
+        if config.model_name.is_empty() {
+            return Err(AxonError::InvalidConfig("model_name cannot be empty".into()));
+        }
+
+        // If we own the process, spawn the launcher
+        if self.owns_process {
+            let tgi_config = TgiConfig::from_model_config(config.clone());
+            let process = TgiProcess::spawn(tgi_config).await?;
+
+            // Wait for TGI to be ready
+            process.wait_until_ready().await?;
+
+            self.client = Some(TgiClient::new(process.base_url()));
+            self.process = Some(process);
+        }
+
+        // Verify the server is responding
+        if let Some(client) = &self.client {
+            client.health_check().await?;
+        }
+
+        self.current_model = Some(config.model_name);
+        Ok(())
+    }
+
+    async fn infer(&self, request: InferenceRequest) -> Result<InferenceResponse> {
+        let limits = RequestLimits::new(request.timeout, request.cancel.as_ref());
+        self.metrics.start().run(async {
+            limits.run(self.ready_client()?.infer(request)).await
+        }).await
+    }
+
+    async fn infer_stream(&self, request: InferenceRequest) -> Result<InferenceStream> {
+        let limits = RequestLimits::new(request.timeout, request.cancel.as_ref());
+        self.metrics.start().run_stream(async {
+            limits.run_stream(self.ready_client()?.infer_stream(request)).await
+        }).await
+    }
+
+    async fn chat(&self, request: ChatRequest) -> Result<InferenceResponse> {
+        let limits = RequestLimits::new(request.timeout, request.cancel.as_ref());
+        self.metrics.start().run(async {
+            limits.run(self.ready_client()?.chat(request)).await
+        }).await
+    }
+
+    async fn health_check(&self) -> HealthStatus {
+        // If we own the process, check if it's running
+        if self.owns_process
+            && let Some(process) = &self.process
+            && let Some(exit) = process.exit_status()
+        {
+            return HealthStatus::Failed(Some(exit));
+        }
+
+        // Check the HTTP API
+        if let Some(client) = &self.client {
+            match client.health_check().await {
+                Ok(_) => HealthStatus::Healthy,
+                Err(_) => HealthStatus::Degraded,
+            }
+        } else {
+            HealthStatus::Starting
+        }
+    }
+
+    fn metrics(&self) -> BackendMetrics {
+        self.metrics.snapshot()
+    }
+
+    async fn shutdown(&mut self) -> Result<()> {
+        // Shutdown the process if we own it
+        if let Some(process) = self.process.take() {
+            process.terminate().await?;
+        }
+
+        self.client = None;
+        self.current_model = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tgi_backend_new() {
+        let backend = TgiBackend::new();
+        assert!(backend.owns_process);
+        assert!(backend.client.is_none());
+    }
+
+    #[test]
+    fn test_tgi_backend_connect_to() {
+        let backend = TgiBackend::connect_to("http://localhost:8080".to_string());
+        assert!(!backend.owns_process);
+        assert!(backend.ready_client().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_infer_before_load_fails() {
+        let backend = TgiBackend::new();
+        let result = backend.infer(InferenceRequest::default()).await;
+        assert!(matches!(result, Err(AxonError::BackendNotRunning)));
+    }
+}
@@ -0,0 +1,510 @@
+//! HTTP client for TGI's native and Messages APIs
+
+use crate::backend::InferenceStream;
+use crate::deadline::{CONNECT_TIMEOUT, CONTROL_TIMEOUT};
+use crate::error::{check_status, AxonError, Result};
+use crate::openai::{OpenAiChatResponse, OpenAiMessage, OpenAiTool, OpenAiToolChoice};
+use crate::sse;
+use crate::types::{
+    ChatRequest, InferenceChunk, InferenceRequest, InferenceResponse, SamplingParams, TokenLogprob, TopLogprob,
+    Usage,
+};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+
+/// HTTP client for communicating with TGI
+pub struct TgiClient {
+    /// Base URL of the TGI server
+    base_url: String,
+
+    /// HTTP client
+    client: reqwest::Client,
+}
+
+impl TgiClient {
+    /// Create a new TGI client
+    pub fn new(base_url: String) -> Self {
+        // Inference calls are bounded per request by `RequestLimits`
+        let client = reqwest::Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .build()
+            .unwrap();
+
+        Self { base_url, client }
+    }
+
+    /// Check if the TGI server is healthy
+    pub async fn health_check(&self) -> Result<()> {
+        let url = format!("{}/health", self.base_url);
+        let resp = self.client.get(&url).timeout(CONTROL_TIMEOUT).send().await?;
+
+        if resp.status().is_success() {
+            Ok(())
+        } else {
+            Err(AxonError::Unhealthy(format!("Status: {}", resp.status())))
+        }
+    }
+
+    /// Run inference on a single prompt via `/generate`
+    pub async fn infer(&self, request: InferenceRequest) -> Result<InferenceResponse> {
+        let tgi_req = TgiGenerateRequest::new(&request)?;
+
+        let start = std::time::Instant::now();
+        let resp = self.post("/generate", &tgi_req).await?;
+        let elapsed = start.elapsed();
+
+        let tgi_resp: TgiGenerateResponse = resp.json().await?;
+        let details = tgi_resp.details
+            .ok_or_else(|| AxonError::InferenceFailed("Response is missing details".into()))?;
+
+        // `/generate` does not report the prompt length
+        let usage = Usage {
+            completion_tokens: details.generated_tokens,
+            ..Default::default()
+        };
+        let logprobs = request.sampling.logprobs.is_some().then(|| {
+            let mut top = details.top_tokens.into_iter();
+            details.tokens.into_iter()
+                .filter_map(|token| token.into_logprob(top.next().unwrap_or_default()))
+                .collect()
+        });
+        // The first prompt token has no logprob and is skipped
+        let prompt_logprobs = request.sampling.prompt_logprobs.is_some().then(|| {
+            details.prefill.into_iter()
+                .filter_map(|token| token.into_logprob(Vec::new()))
+                .collect()
+        });
+
+        Ok(InferenceResponse {
+            text: tgi_resp.generated_text,
+            tokens_generated: usage.completion_tokens,
+            usage,
+            inference_time: elapsed.as_secs_f64(),
+            tokens_per_second: usage.tokens_per_second(elapsed),
+            finish_reason: finish_reason(&details.finish_reason).to_string(),
+            request_id: request.request_id,
+            attempts: 1,
+            logprobs,
+            prompt_logprobs,
+            tool_calls: Vec::new(),
+        })
+    }
+
+    /// Run inference on a single prompt, streaming tokens via `/generate_stream`
+    ///
+    /// TGI cannot return prompt logprobs while streaming, so
+    /// `prompt_logprobs` is rejected.
+    pub async fn infer_stream(&self, request: InferenceRequest) -> Result<InferenceStream> {
+        if request.sampling.prompt_logprobs.is_some() {
+            return Err(AxonError::InvalidConfig("prompt_logprobs is only supported by infer".into()));
+        }
+        let tgi_req = TgiGenerateRequest::new(&request)?;
+        let resp = self.post("/generate_stream", &tgi_req).await?;
+        let logprobs = request.sampling.logprobs.is_some();
+
+        let chunks = sse::events(resp.bytes_stream())
+            .map(move |event| event.and_then(|data| parse_stream_event(data, logprobs)));
+
+        Ok(Box::pin(chunks))
+    }
+
+    /// Run a chat completion via TGI's OpenAI-compatible Messages API
+    pub async fn chat(&self, request: ChatRequest) -> Result<InferenceResponse> {
+        let tgi_req = TgiChatRequest::new(&request)?;
+
+        let start = std::time::Instant::now();
+        let resp = self.post("/v1/chat/completions", &tgi_req).await?;
+        let elapsed = start.elapsed();
+
+        let tgi_resp: OpenAiChatResponse = resp.json().await?;
+
+        let mut choice = tgi_resp.choices.into_iter().next()
+            .ok_or_else(|| AxonError::InferenceFailed("No choices in response".into()))?;
+        let usage = tgi_resp.usage.map(Usage::from).unwrap_or_default();
+        let logprobs = choice.logprobs.map(|l| l.into_tokens(None));
+        let tool_calls = choice.message.take_tool_calls();
+
+        Ok(InferenceResponse {
+            text: choice.message.content.unwrap_or_default(),
+            tokens_generated: usage.completion_tokens,
+            usage,
+            inference_time: elapsed.as_secs_f64(),
+            tokens_per_second: usage.tokens_per_second(elapsed),
+            finish_reason: finish_reason(choice.finish_reason.as_deref().unwrap_or_default()).to_string(),
+            request_id: request.request_id,
+            attempts: 1,
+            logprobs,
+            prompt_logprobs: None,
+            tool_calls,
+        })
+    }
+
+    /// POST a JSON body, turning non-success statuses into errors
+    async fn post<T: Serialize>(&self, path: &str, body: &T) -> Result<reqwest::Response> {
+        let url = format!("{}{}", self.base_url, path);
+        let resp = self.client.post(&url).json(body).send().await?;
+
+        check_status(resp).await
+    }
+}
+
+/// Normalize TGI finish reasons to Axon's "length" / "stop" vocabulary
+fn finish_reason(reason: &str) -> &str {
+    match reason {
+        "eos_token" | "stop_sequence" => "stop",
+        other => other,
+    }
+}
+
+/// Optional sampling parameters accepted by `/generate`
+const GENERATE_OPTIONS: &[&str] = &["frequency_penalty", "seed", "repetition_penalty", "logprobs", "prompt_logprobs"];
+
+/// Optional sampling parameters accepted by the Messages API
+const CHAT_OPTIONS: &[&str] = &["frequency_penalty", "seed", "logprobs"];
+
+/// Convert one SSE payload into a chunk, attaching the token's logprob if
+/// `logprobs` were requested
+fn parse_stream_event(data: String, logprobs: bool) -> Result<InferenceChunk> {
+    let event: TgiStreamResponse = serde_json::from_str(&data)
+        .map_err(|e| AxonError::InferenceFailed(format!("Invalid stream event: {}", e)))?;
+
+    if let Some(error) = event.error {
+        return Err(AxonError::InferenceFailed(error));
+    }
+
+    let token = event.token
+        .ok_or_else(|| AxonError::InferenceFailed("Stream event has no token".into()))?;
+    let finish = event.details.as_ref().map(|d| finish_reason(&d.finish_reason).to_string());
+    let usage = event.details.map(|d| Usage {
+        completion_tokens: d.generated_tokens,
+        ..Default::default()
+    });
+
+    let text_delta = if token.special { String::new() } else { token.text.clone() };
+    let logprobs = if logprobs {
+        Some(token.into_logprob(event.top_tokens).into_iter().collect())
+    } else {
+        None
+    };
+
+    Ok(InferenceChunk {
+        text_delta,
+        finished: finish.is_some() || event.generated_text.is_some(),
+        finish_reason: finish,
+        usage,
+        logprobs,
+    })
+}
+
+/// Generation parameters for TGI's native API
+#[derive(Debug, Serialize)]
+struct TgiParameters {
+    max_new_tokens: u32,
+    do_sample: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_k: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frequency_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    stop: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    repetition_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_n_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    decoder_input_details: bool,
+    details: bool,
+    /// LoRA adapter to generate with, or the base model if unset
+    #[serde(skip_serializing_if = "Option::is_none")]
+    adapter_id: Option<String>,
+}
+
+impl From<&SamplingParams> for TgiParameters {
+    fn from(sampling: &SamplingParams) -> Self {
+        // TGI requires temperature > 0 and top_p < 1; greedy decoding and
+        // "no nucleus filtering" are expressed by omitting them instead.
+        let do_sample = sampling.temperature > 0.0;
+
+        Self {
+            max_new_tokens: sampling.max_tokens,
+            do_sample,
+            temperature: do_sample.then_some(sampling.temperature),
+            top_p: sampling.top_p.filter(|p| *p < 1.0),
+            top_k: sampling.top_k,
+            frequency_penalty: sampling.frequency_penalty.filter(|p| *p != 0.0),
+            stop: sampling.stop_sequences.clone(),
+            seed: sampling.seed,
+            repetition_penalty: sampling.repetition_penalty.filter(|p| *p != 1.0),
+            top_n_tokens: sampling.logprobs.filter(|n| *n > 0),
+            // TGI reports no alternatives for prompt tokens
+            decoder_input_details: sampling.prompt_logprobs.is_some(),
+            details: true,
+            adapter_id: None,
+        }
+    }
+}
+
+/// TGI `/generate` request format
+#[derive(Debug, Serialize)]
+struct TgiGenerateRequest {
+    inputs: String,
+    parameters: TgiParameters,
+}
+
+impl TgiGenerateRequest {
+    /// Build the wire request for an Axon inference request
+    fn new(request: &InferenceRequest) -> Result<Self> {
+        request.sampling.check_supported("TGI", GENERATE_OPTIONS)?;
+
+        Ok(Self {
+            inputs: request.prompt.clone(),
+            parameters: TgiParameters {
+                adapter_id: request.model.clone(),
+                ..TgiParameters::from(&request.sampling)
+            },
+        })
+    }
+}
+
+/// TGI Messages API request format
+#[derive(Debug, Serialize)]
+struct TgiChatRequest {
+    model: String,
+    messages: Vec<OpenAiMessage>,
+    max_tokens: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    frequency_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    stop: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    logprobs: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_logprobs: Option<u32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<OpenAiTool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<OpenAiToolChoice>,
+}
+
+impl TgiChatRequest {
+    /// Build the wire request for an Axon chat request
+    fn new(request: &ChatRequest) -> Result<Self> {
+        request.sampling.check_supported("TGI", CHAT_OPTIONS)?;
+        let params = TgiParameters::from(&request.sampling);
+
+        Ok(Self {
+            // TGI treats any name other than "tgi" as a LoRA adapter ID
+            model: request.model.clone().unwrap_or_else(|| "tgi".to_string()),
+            messages: request.messages.iter().map(OpenAiMessage::from).collect(),
+            max_tokens: params.max_new_tokens,
+            temperature: params.temperature,
+            top_p: params.top_p,
+            frequency_penalty: params.frequency_penalty,
+            stop: params.stop,
+            seed: params.seed,
+            logprobs: request.sampling.logprobs.is_some(),
+            top_logprobs: params.top_n_tokens,
+            tools: request.tools.iter().map(OpenAiTool::from).collect(),
+            tool_choice: request.tool_choice.as_ref().map(OpenAiToolChoice::from),
+        })
+    }
+}
+
+/// TGI `/generate` response format
+#[derive(Debug, Deserialize)]
+struct TgiGenerateResponse {
+    generated_text: String,
+    details: Option<TgiDetails>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TgiDetails {
+    finish_reason: String,
+    generated_tokens: usize,
+    #[serde(default)]
+    tokens: Vec<TgiToken>,
+    #[serde(default)]
+    top_tokens: Vec<Vec<TgiToken>>,
+    #[serde(default)]
+    prefill: Vec<TgiToken>,
+}
+
+/// A single server-sent event from `/generate_stream`
+#[derive(Debug, Deserialize)]
+struct TgiStreamResponse {
+    token: Option<TgiToken>,
+    generated_text: Option<String>,
+    details: Option<TgiDetails>,
+    error: Option<String>,
+    #[serde(default)]
+    top_tokens: Vec<TgiToken>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TgiToken {
+    #[serde(default)]
+    id: Option<u32>,
+    text: String,
+    #[serde(default)]
+    logprob: Option<f32>,
+    #[serde(default)]
+    special: bool,
+}
+
+impl TgiToken {
+    /// Convert to Axon's format with `top` as alternatives, or `None` if the
+    /// token has no logprob
+    fn into_logprob(self, top: Vec<TgiToken>) -> Option<TokenLogprob> {
+        Some(TokenLogprob {
+            logprob: self.logprob?,
+            token: self.text,
+            token_id: self.id,
+            top_logprobs: top.into_iter().filter_map(|alt| Some(TopLogprob {
+                logprob: alt.logprob?,
+                token: alt.text,
+                token_id: alt.id,
+            })).collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generate_request_serialization() {
+        let request = InferenceRequest {
+            prompt: "Hello".to_string(),
+            sampling: SamplingParams {
+                temperature: 0.0,
+                stop_sequences: vec!["\n".to_string()],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let json = serde_json::to_value(TgiGenerateRequest::new(&request).unwrap()).unwrap();
+        assert_eq!(json["inputs"], "Hello");
+        assert_eq!(json["parameters"]["max_new_tokens"], 100);
+        assert_eq!(json["parameters"]["do_sample"], false);
+        assert!(json["parameters"].get("temperature").is_none());
+        assert!(json["parameters"].get("top_p").is_none());
+        assert_eq!(json["parameters"]["stop"][0], "\n");
+    }
+
+    #[test]
+    fn test_model_selects_adapter() {
+        let request = InferenceRequest {
+            model: Some("sql-lora".to_string()),
+            ..Default::default()
+        };
+        let json = serde_json::to_value(TgiGenerateRequest::new(&request).unwrap()).unwrap();
+        assert_eq!(json["parameters"]["adapter_id"], "sql-lora");
+
+        let base = serde_json::to_value(TgiGenerateRequest::new(&InferenceRequest::default()).unwrap()).unwrap();
+        assert!(base["parameters"].get("adapter_id").is_none());
+
+        let chat = ChatRequest {
+            model: Some("sql-lora".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(serde_json::to_value(TgiChatRequest::new(&chat).unwrap()).unwrap()["model"], "sql-lora");
+    }
+
+    #[test]
+    fn test_presence_penalty_rejected() {
+        let request = InferenceRequest {
+            sampling: SamplingParams {
+                presence_penalty: Some(0.5),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert!(matches!(TgiGenerateRequest::new(&request), Err(AxonError::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn test_extended_sampling_mapped_or_rejected() {
+        let mut request = InferenceRequest {
+            sampling: SamplingParams {
+                seed: Some(7),
+                repetition_penalty: Some(1.2),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let json = serde_json::to_value(TgiGenerateRequest::new(&request).unwrap()).unwrap();
+        assert_eq!(json["parameters"]["seed"], 7);
+        assert_eq!(json["parameters"]["repetition_penalty"], 1.2f32);
+
+        request.sampling.min_p = Some(0.1);
+        let err = TgiGenerateRequest::new(&request).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid configuration: TGI does not support min_p");
+    }
+
+    #[test]
+    fn test_parse_stream_event() {
+        let chunk = parse_stream_event(
+            r#"{"index":1,"token":{"id":1917,"text":" world","logprob":-0.3,"special":false},"generated_text":null,"details":null}"#.to_string(),
+            false,
+        )
+        .unwrap();
+        assert_eq!(chunk.text_delta, " world");
+        assert!(!chunk.finished);
+
+        let last = parse_stream_event(
+            r#"{"index":2,"token":{"id":2,"text":"</s>","logprob":-0.1,"special":true},"generated_text":"Hello world","details":{"finish_reason":"eos_token","generated_tokens":2,"seed":null}}"#.to_string(),
+            false,
+        )
+        .unwrap();
+        assert_eq!(last.text_delta, "");
+        assert!(last.finished);
+        assert_eq!(last.finish_reason.as_deref(), Some("stop"));
+        assert!(last.logprobs.is_none());
+
+        let chunk = parse_stream_event(
+            r#"{"index":1,"token":{"id":1917,"text":" world","logprob":-0.3,"special":false},"top_tokens":[{"id":1917,"text":" world","logprob":-0.3,"special":false},{"id":1342,"text":" there","logprob":-1.9,"special":false}]}"#.to_string(),
+            true,
+        )
+        .unwrap();
+        let logprobs = chunk.logprobs.unwrap();
+        assert_eq!(logprobs[0].token_id, Some(1917));
+        assert_eq!(logprobs[0].top_logprobs[1].token, " there");
+    }
+
+    #[tokio::test]
+    async fn test_logprobs_requested() {
+        let request = InferenceRequest {
+            sampling: SamplingParams {
+                logprobs: Some(3),
+                prompt_logprobs: Some(0),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let json = serde_json::to_value(TgiGenerateRequest::new(&request).unwrap()).unwrap();
+        assert_eq!(json["parameters"]["top_n_tokens"], 3);
+        assert_eq!(json["parameters"]["decoder_input_details"], true);
+
+        // Rejected before anything is sent, so no server is needed
+        let client = TgiClient::new("http://127.0.0.1:1".to_string());
+        let result = client.infer_stream(request).await;
+        assert!(matches!(result, Err(AxonError::InvalidConfig(_))));
+    }
+}
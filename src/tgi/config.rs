@@ -0,0 +1,147 @@
+//! TGI-specific configuration
+
+use crate::error::{AxonError, Result};
+use crate::logs::{LogSinks, TracingSink};
+use crate::types::ModelConfig;
+use std::sync::Arc;
+
+/// TGI-specific configuration derived from ModelConfig
+#[derive(Debug, Clone)]
+pub struct TgiConfig {
+    /// HuggingFace model ID or local path
+    pub model_id: String,
+
+    /// Host to bind to
+    pub hostname: String,
+
+    /// Port to bind to
+    pub port: u16,
+
+    /// Number of shards (GPUs) to split the model across
+    pub num_shard: Option<usize>,
+
+    /// Maximum prompt plus generated tokens per request
+    pub max_total_tokens: Option<usize>,
+
+    /// Maximum tokens across all requests in a batch
+    ///
+    /// Must be at least `max_total_tokens`. TGI sizes it from free GPU
+    /// memory when unset.
+    pub max_batch_total_tokens: Option<usize>,
+
+    /// Maximum requests handled at once; further requests are rejected
+    pub max_concurrent_requests: Option<usize>,
+
+    /// Data type (float16, bfloat16)
+    pub dtype: Option<String>,
+
+    /// Where launcher stdout/stderr lines are forwarded
+    ///
+    /// Defaults to a `TracingSink`. The pipes are drained even when empty.
+    pub log_sinks: LogSinks,
+
+    /// Number of recent output lines attached to startup errors
+    pub log_tail_lines: usize,
+}
+
+impl TgiConfig {
+    /// Create TGI config from generic ModelConfig
+    ///
+    /// vLLM-style dtype names are translated: `half` becomes `float16`, and
+    /// `auto` leaves the choice to TGI.
+    pub fn from_model_config(config: ModelConfig) -> Self {
+        let dtype = config.dtype.and_then(|dtype| match dtype.as_str() {
+            "auto" => None,
+            "half" | "fp16" => Some("float16".to_string()),
+            "bf16" => Some("bfloat16".to_string()),
+            _ => Some(dtype),
+        });
+
+        Self {
+            model_id: config.model_name,
+            hostname: config.host.unwrap_or_else(|| "127.0.0.1".to_string()),
+            port: config.port.unwrap_or(8080),
+            num_shard: config.tensor_parallel_size,
+            max_total_tokens: config.max_sequence_length,
+            max_batch_total_tokens: None,
+            max_concurrent_requests: config.max_batch_size,
+            dtype,
+            log_sinks: default_log_sinks(),
+            log_tail_lines: 50,
+        }
+    }
+
+    /// Check the limits TGI enforces between its options at startup
+    pub fn validate(&self) -> Result<()> {
+        if let (Some(batch_total), Some(total)) = (self.max_batch_total_tokens, self.max_total_tokens)
+            && batch_total < total
+        {
+            return Err(AxonError::InvalidConfig(format!(
+                "max_batch_total_tokens ({}) must be at least max_total_tokens ({})",
+                batch_total, total
+            )));
+        }
+        Ok(())
+    }
+
+    /// Base URL of the server's HTTP API
+    pub fn base_url(&self) -> String {
+        format!("http://{}:{}", self.hostname, self.port)
+    }
+}
+
+/// Launcher output goes to `tracing` unless configured otherwise
+fn default_log_sinks() -> LogSinks {
+    let mut sinks = LogSinks::new();
+    sinks.push(Arc::new(TracingSink::new("tgi")));
+    sinks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_model_config() {
+        let model_config = ModelConfig {
+            model_name: "bigscience/bloom-560m".to_string(),
+            port: Some(3000),
+            tensor_parallel_size: Some(2),
+            dtype: Some("half".to_string()),
+            ..Default::default()
+        };
+
+        let tgi_config = TgiConfig::from_model_config(model_config);
+
+        assert_eq!(tgi_config.model_id, "bigscience/bloom-560m");
+        assert_eq!(tgi_config.num_shard, Some(2));
+        assert_eq!(tgi_config.dtype.as_deref(), Some("float16"));
+        assert_eq!(tgi_config.base_url(), "http://127.0.0.1:3000");
+    }
+
+    #[test]
+    fn test_auto_dtype_is_omitted() {
+        let tgi_config = TgiConfig::from_model_config(ModelConfig {
+            model_name: "test-model".to_string(),
+            ..Default::default()
+        });
+
+        assert_eq!(tgi_config.dtype, None);
+    }
+
+    #[test]
+    fn test_default_config_is_valid() {
+        let tgi_config = TgiConfig::from_model_config(ModelConfig::default());
+
+        // `max_batch_size` limits concurrent requests, as `max_num_seqs` does for vLLM
+        assert_eq!(tgi_config.max_concurrent_requests, None);
+        assert_eq!(tgi_config.max_batch_total_tokens, None);
+        assert!(tgi_config.validate().is_ok());
+
+        let too_small = TgiConfig {
+            max_batch_total_tokens: Some(256),
+            ..tgi_config
+        };
+        assert!(matches!(too_small.validate(), Err(AxonError::InvalidConfig(_))));
+    }
+}
@@ -0,0 +1,198 @@
+//! TGI process management
+//!
+//! Handles spawning, monitoring, and terminating `text-generation-launcher`.
+//!
+//! The launcher runs as an `EngineProcess`, leading its own process group,
+//! so the shards and webserver it starts are signalled with it.
+
+use crate::backend::ProcessExit;
+use crate::error::{AxonError, Result};
+use crate::logs;
+use crate::process::EngineProcess;
+use std::time::Duration;
+use tokio::process::Command;
+use tokio::time::{sleep, Instant};
+
+use super::config::TgiConfig;
+
+/// How long `terminate` waits after SIGTERM before sending SIGKILL
+const TERMINATE_GRACE: Duration = Duration::from_secs(5);
+
+/// How long TGI may take to download and shard weights before it is ready
+const READY_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// A running TGI launcher process
+pub struct TgiProcess {
+    /// The launcher and its shards
+    engine: EngineProcess,
+
+    /// Configuration the launcher was started with
+    config: TgiConfig,
+}
+
+impl TgiProcess {
+    /// Spawn a new `text-generation-launcher` process
+    pub async fn spawn(config: TgiConfig) -> Result<Self> {
+        config.validate()?;
+        let mut cmd = Command::new("text-generation-launcher");
+        cmd.args(Self::args(&config));
+
+        Self::start(cmd, config)
+    }
+
+    /// Start `cmd` as the launcher for `config`
+    fn start(cmd: Command, config: TgiConfig) -> Result<Self> {
+        let engine = EngineProcess::start(cmd, "TGI", config.log_sinks.clone(), config.log_tail_lines)?;
+        Ok(Self { engine, config })
+    }
+
+    /// Build the launcher command line
+    fn args(config: &TgiConfig) -> Vec<String> {
+        let mut args = vec![
+            "--model-id".to_string(),
+            config.model_id.clone(),
+            "--hostname".to_string(),
+            config.hostname.clone(),
+            "--port".to_string(),
+            config.port.to_string(),
+        ];
+
+        if let Some(shards) = config.num_shard {
+            args.push("--num-shard".to_string());
+            args.push(shards.to_string());
+        }
+
+        if let Some(max_total) = config.max_total_tokens {
+            args.push("--max-total-tokens".to_string());
+            args.push(max_total.to_string());
+        }
+
+        if let Some(batch_total) = config.max_batch_total_tokens {
+            args.push("--max-batch-total-tokens".to_string());
+            args.push(batch_total.to_string());
+        }
+
+        if let Some(concurrent) = config.max_concurrent_requests {
+            args.push("--max-concurrent-requests".to_string());
+            args.push(concurrent.to_string());
+        }
+
+        if let Some(dtype) = &config.dtype {
+            args.push("--dtype".to_string());
+            args.push(dtype.clone());
+        }
+
+        args
+    }
+
+    /// Check if the launcher is still running
+    pub fn is_running(&self) -> bool {
+        self.engine.is_running()
+    }
+
+    /// How the launcher ended, or `None` while it is still running
+    pub fn exit_status(&self) -> Option<ProcessExit> {
+        self.engine.exit_status()
+    }
+
+    /// The most recent lines the launcher wrote to stdout/stderr
+    pub fn recent_output(&self) -> Vec<String> {
+        self.engine.recent_output()
+    }
+
+    /// Wait until TGI is ready to serve requests
+    ///
+    /// TGI downloads and shards weights before its router starts answering,
+    /// so this polls `/health` for up to ten minutes. Fails as soon as the
+    /// launcher exits.
+    pub async fn wait_until_ready(&self) -> Result<()> {
+        let url = format!("{}/health", self.config.base_url());
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(5))
+            .build()?;
+        let deadline = Instant::now() + READY_TIMEOUT;
+
+        loop {
+            if let Some(exit) = self.exit_status() {
+                return Err(AxonError::ModelLoadFailed(logs::with_tail(
+                    format!("TGI exited during startup ({})", exit),
+                    &self.recent_output(),
+                )));
+            }
+
+            if let Ok(resp) = client.get(&url).send().await
+                && resp.status().is_success()
+            {
+                return Ok(());
+            }
+
+            if Instant::now() >= deadline {
+                return Err(AxonError::ModelLoadFailed(logs::with_tail(
+                    "TGI did not become ready in time".to_string(),
+                    &self.recent_output(),
+                )));
+            }
+
+            // Wake early if the launcher dies between probes
+            tokio::select! {
+                _ = sleep(Duration::from_secs(2)) => {}
+                _ = self.engine.wait() => {}
+            }
+        }
+    }
+
+    /// Base URL of the launched server
+    pub fn base_url(&self) -> String {
+        self.config.base_url()
+    }
+
+    /// Terminate the launcher, its shards and webserver
+    ///
+    /// Sends SIGTERM to the process group, then SIGKILL if any member is
+    /// still alive after a grace period.
+    pub async fn terminate(self) -> Result<ProcessExit> {
+        Ok(self.engine.terminate(TERMINATE_GRACE).await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> TgiConfig {
+        TgiConfig {
+            model_id: "test-model".to_string(),
+            hostname: "0.0.0.0".to_string(),
+            port: 3000,
+            num_shard: Some(2),
+            max_total_tokens: None,
+            max_batch_total_tokens: Some(8192),
+            max_concurrent_requests: Some(128),
+            dtype: None,
+            log_sinks: Default::default(),
+            log_tail_lines: 10,
+        }
+    }
+
+    #[test]
+    fn test_launcher_args() {
+        let args = TgiProcess::args(&config());
+        assert_eq!(&args[..6], ["--model-id", "test-model", "--hostname", "0.0.0.0", "--port", "3000"]);
+        assert!(args.windows(2).any(|w| w == ["--num-shard", "2"]));
+        assert!(args.windows(2).any(|w| w == ["--max-batch-total-tokens", "8192"]));
+        assert!(args.windows(2).any(|w| w == ["--max-concurrent-requests", "128"]));
+        assert!(!args.contains(&"--dtype".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_crashed_launcher_is_reported() {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg("echo 'Shard 0 failed to start' >&2; exit 1");
+        let process = TgiProcess::start(cmd, config()).unwrap();
+
+        let err = process.wait_until_ready().await.unwrap_err().to_string();
+        assert!(err.contains("exit code 1"), "{}", err);
+        assert!(err.contains("Shard 0 failed to start"), "{}", err);
+        assert!(!process.is_running());
+    }
+}
@@ -0,0 +1,165 @@
+//! TensorRT-LLM backend implementation via Triton Inference Server
+//!
+//! This module provides an Axon backend for TensorRT-LLM engines served by
+//! Triton, using the KServe v2 HTTP protocol and Triton's generate extension.
+//! Triton is expected to be running already; Axon does not build engines or
+//! launch `tritonserver`.
+
+pub mod client;
+pub mod config;
+
+use crate::backend::{BackendMetrics, HealthStatus, InferenceBackend, InferenceStream};
+use crate::error::{AxonError, Result};
+use crate::deadline::RequestLimits;
+use crate::metrics::MetricsTracker;
+use crate::types::{ChatRequest, InferenceRequest, InferenceResponse, ModelConfig};
+use std::sync::Arc;
+
+use client::TritonClient;
+use config::TritonConfig;
+
+/// TensorRT-LLM / Triton backend for Axon
+///
+/// `load_model` selects which Triton model (typically `ensemble` or
+/// `tensorrt_llm_bls`) serves requests, asking the model repository to load
+/// it if it is not ready yet.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axon::{InferenceBackend, triton::TritonBackend, ModelConfig, InferenceRequest};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let mut backend = TritonBackend::connect_to("http://localhost:8000".to_string());
+///
+/// backend.load_model(ModelConfig {
+///     model_name: "ensemble".to_string(),
+///     ..Default::default()
+/// }).await?;
+///
+/// let response = backend.infer(InferenceRequest {
+///     prompt: "Explain Rust in one sentence.".to_string(),
+///     ..Default::default()
+/// }).await?;
+///
+/// println!("{}", response.text);
+/// # Ok(())
+/// # }
+/// ```
+pub struct TritonBackend {
+    /// HTTP client for communicating with Triton
+    client: TritonClient,
+
+    /// Model selected by `load_model`
+    config: Option<TritonConfig>,
+
+    /// Metrics tracker, shared with in-flight streams
+    metrics: Arc<MetricsTracker>,
+}
+
+impl TritonBackend {
+    /// Create a Triton backend that connects to an existing Triton server
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - The base URL of Triton's HTTP endpoint (e.g., "http://localhost:8000")
+    pub fn connect_to(base_url: String) -> Self {
+        Self {
+            client: TritonClient::new(base_url),
+            config: None,
+            metrics: MetricsTracker::new(),
+        }
+    }
+
+    /// Get the selected model, failing if `load_model` has not been called
+    fn model(&self) -> Result<&TritonConfig> {
+        self.config.as_ref().ok_or(AxonError::BackendNotRunning)
+    }
+}
+
+impl InferenceBackend for TritonBackend {
+    async fn load_model(&mut self, config: ModelConfig) -> Result<()> {
+        // Validate configuration
+        if config.model_name.is_empty() {
+            return Err(AxonError::InvalidConfig("model_name cannot be empty".into()));
+        }
+
+        let triton_config = TritonConfig::from_model_config(config)?;
+
+        // Verify the server is responding
+        self.client.health_check().await?;
+
+        // Load through the repository API if the model is not already live
+        if self.client.model_ready(&triton_config).await.is_err() {
+            self.client.load_model(&triton_config).await?;
+            self.client.model_ready(&triton_config).await?;
+        }
+
+        self.config = Some(triton_config);
+        Ok(())
+    }
+
+    async fn infer(&self, request: InferenceRequest) -> Result<InferenceResponse> {
+        let limits = RequestLimits::new(request.timeout, request.cancel.as_ref());
+        self.metrics.start().run(async {
+            limits.run(self.client.infer(self.model()?, request)).await
+        }).await
+    }
+
+    async fn infer_stream(&self, request: InferenceRequest) -> Result<InferenceStream> {
+        let limits = RequestLimits::new(request.timeout, request.cancel.as_ref());
+        self.metrics.start().run_stream(async {
+            limits.run_stream(self.client.infer_stream(self.model()?, request)).await
+        }).await
+    }
+
+    async fn chat(&self, _request: ChatRequest) -> Result<InferenceResponse> {
+        Err(AxonError::BackendError(
+            "Triton has no chat template support; format the prompt and use infer()".into(),
+        ))
+    }
+
+    async fn health_check(&self) -> HealthStatus {
+        if self.client.health_check().await.is_err() {
+            return HealthStatus::Degraded;
+        }
+
+        match &self.config {
+            Some(config) => match self.client.model_ready(config).await {
+                Ok(_) => HealthStatus::Healthy,
+                Err(_) => HealthStatus::Degraded,
+            },
+            None => HealthStatus::Starting,
+        }
+    }
+
+    fn metrics(&self) -> BackendMetrics {
+        self.metrics.snapshot()
+    }
+
+    async fn shutdown(&mut self) -> Result<()> {
+        // The server is not ours; just stop routing requests to it
+        self.config = None;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_infer_before_load_fails() {
+        let backend = TritonBackend::connect_to("http://localhost:8000".to_string());
+        let result = backend.infer(InferenceRequest::default()).await;
+        assert!(matches!(result, Err(AxonError::BackendNotRunning)));
+    }
+
+    #[tokio::test]
+    async fn test_chat_unsupported() {
+        let backend = TritonBackend::connect_to("http://localhost:8000".to_string());
+        let result = backend.chat(ChatRequest::default()).await;
+        assert!(matches!(result, Err(AxonError::BackendError(_))));
+    }
+}
@@ -0,0 +1,433 @@
+//! HTTP client for Triton's KServe v2 protocol and generate extension
+
+use crate::backend::InferenceStream;
+use crate::deadline::{CONNECT_TIMEOUT, CONTROL_TIMEOUT, DEFAULT_REQUEST_TIMEOUT};
+use crate::error::{check_status, AxonError, Result};
+use crate::sse;
+use crate::types::{InferenceChunk, InferenceRequest, InferenceResponse, SamplingParams, Usage};
+use futures_util::{future, stream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use super::config::{TritonConfig, TritonProtocol};
+
+/// HTTP client for communicating with Triton Inference Server
+pub struct TritonClient {
+    /// Base URL of the Triton HTTP endpoint
+    base_url: String,
+
+    /// HTTP client
+    client: reqwest::Client,
+}
+
+impl TritonClient {
+    /// Create a new Triton client
+    pub fn new(base_url: String) -> Self {
+        // Inference calls are bounded per request by `RequestLimits`
+        let client = reqwest::Client::builder()
+            .connect_timeout(CONNECT_TIMEOUT)
+            .build()
+            .unwrap();
+
+        Self { base_url, client }
+    }
+
+    /// Check if the Triton server is ready via `/v2/health/ready`
+    pub async fn health_check(&self) -> Result<()> {
+        self.get_ok("/v2/health/ready").await
+    }
+
+    /// Check if a specific model is loaded and ready
+    pub async fn model_ready(&self, config: &TritonConfig) -> Result<()> {
+        self.get_ok(&format!("{}/ready", config.model_path())).await
+    }
+
+    /// Ask the model repository to load a model
+    ///
+    /// Only succeeds when Triton runs with `--model-control-mode=explicit`.
+    pub async fn load_model(&self, config: &TritonConfig) -> Result<()> {
+        let url = format!("{}/v2/repository/models/{}/load", self.base_url, config.model_name);
+        let resp = self.client.post(&url).timeout(DEFAULT_REQUEST_TIMEOUT).send().await?;
+
+        if resp.status().is_success() {
+            Ok(())
+        } else {
+            let status = resp.status();
+            let text = resp.text().await.unwrap_or_default();
+            Err(AxonError::ModelLoadFailed(format!("{}: {}", status, text)))
+        }
+    }
+
+    /// Run inference on a single prompt using the configured protocol
+    pub async fn infer(&self, config: &TritonConfig, request: InferenceRequest) -> Result<InferenceResponse> {
+        check_request(&request)?;
+        let start = std::time::Instant::now();
+
+        let text = match config.protocol {
+            TritonProtocol::Infer => {
+                let body = infer_request(&request);
+                let resp = self.post(&format!("{}/infer", config.model_path()), &body).await?;
+                let triton_resp: KserveInferResponse = resp.json().await?;
+                triton_resp.text_output()?
+            }
+            TritonProtocol::Generate => {
+                let body = generate_request(&request, false);
+                let resp = self.post(&format!("{}/generate", config.model_path()), &body).await?;
+                let triton_resp: TritonGenerateResponse = resp.json().await?;
+                triton_resp.text_output
+            }
+        };
+
+        // Triton's LLM models do not report token counts or finish reasons,
+        // so usage stays zero rather than being guessed
+        Ok(InferenceResponse {
+            text,
+            tokens_generated: 0,
+            usage: Usage::default(),
+            inference_time: start.elapsed().as_secs_f64(),
+            tokens_per_second: 0.0,
+            finish_reason: FINISH_UNKNOWN.to_string(),
+            request_id: request.request_id,
+            attempts: 1,
+            logprobs: None,
+            prompt_logprobs: None,
+            tool_calls: Vec::new(),
+        })
+    }
+
+    /// Run inference on a single prompt, streaming via `generate_stream`
+    ///
+    /// Streaming always uses the generate extension; the KServe v2 HTTP
+    /// protocol has no streaming mode. Triton does not signal why generation
+    /// ended, so a final chunk is synthesized when the stream closes without
+    /// an error.
+    pub async fn infer_stream(&self, config: &TritonConfig, request: InferenceRequest) -> Result<InferenceStream> {
+        check_request(&request)?;
+        let body = generate_request(&request, true);
+        let resp = self.post(&format!("{}/generate_stream", config.model_path()), &body).await?;
+
+        let chunks = sse::events(resp.bytes_stream()).map(|event| event.and_then(parse_stream_event));
+
+        Ok(Box::pin(with_finished_chunk(chunks)))
+    }
+
+    /// GET a path, treating any non-success status as unhealthy
+    async fn get_ok(&self, path: &str) -> Result<()> {
+        let url = format!("{}{}", self.base_url, path);
+        let resp = self.client.get(&url).timeout(CONTROL_TIMEOUT).send().await?;
+
+        if resp.status().is_success() {
+            Ok(())
+        } else {
+            Err(AxonError::Unhealthy(format!("Status: {}", resp.status())))
+        }
+    }
+
+    /// POST a JSON body, turning non-success statuses into errors
+    async fn post<T: Serialize>(&self, path: &str, body: &T) -> Result<reqwest::Response> {
+        let url = format!("{}{}", self.base_url, path);
+        let resp = self.client.post(&url).json(body).send().await?;
+
+        check_status(resp).await
+    }
+}
+
+/// Finish reason reported for Triton, which does not say why generation ended
+const FINISH_UNKNOWN: &str = "unknown";
+
+/// Optional sampling parameters with a TensorRT-LLM input
+const SUPPORTED_OPTIONS: &[&str] = &[
+    "presence_penalty",
+    "frequency_penalty",
+    "seed",
+    "repetition_penalty",
+    "min_tokens",
+    "bad_words",
+];
+
+/// Reject requests Triton cannot honor rather than silently dropping options
+///
+/// Requests always go to the model selected by `load_model`, so naming
+/// another model is an error.
+fn check_request(request: &InferenceRequest) -> Result<()> {
+    if let Some(model) = &request.model {
+        return Err(AxonError::InvalidConfig(format!(
+            "Triton cannot route requests to model {}; select it with load_model",
+            model
+        )));
+    }
+    request.sampling.check_supported("Triton", SUPPORTED_OPTIONS)
+}
+
+/// Named sampling inputs understood by the TensorRT-LLM `ensemble` and
+/// `tensorrt_llm_bls` models, as (name, KServe datatype, value)
+fn sampling_inputs(sampling: &SamplingParams) -> Vec<(&'static str, &'static str, Value)> {
+    let mut inputs = vec![("max_tokens", "INT32", json!(sampling.max_tokens))];
+
+    // TensorRT-LLM has no temperature-zero mode; greedy decoding is top_k = 1
+    if sampling.temperature > 0.0 {
+        inputs.push(("temperature", "FP32", json!(sampling.temperature)));
+        if let Some(top_k) = sampling.top_k {
+            inputs.push(("top_k", "INT32", json!(top_k)));
+        }
+    } else {
+        inputs.push(("top_k", "INT32", json!(1)));
+    }
+
+    if let Some(top_p) = sampling.top_p {
+        inputs.push(("top_p", "FP32", json!(top_p)));
+    }
+    if let Some(penalty) = sampling.presence_penalty {
+        inputs.push(("presence_penalty", "FP32", json!(penalty)));
+    }
+    if let Some(penalty) = sampling.frequency_penalty {
+        inputs.push(("frequency_penalty", "FP32", json!(penalty)));
+    }
+    if let Some(penalty) = sampling.repetition_penalty {
+        inputs.push(("repetition_penalty", "FP32", json!(penalty)));
+    }
+    if let Some(min_tokens) = sampling.min_tokens {
+        inputs.push(("min_length", "INT32", json!(min_tokens)));
+    }
+    if let Some(seed) = sampling.seed {
+        inputs.push(("random_seed", "UINT64", json!(seed)));
+    }
+
+    inputs
+}
+
+/// Build a KServe v2 `/infer` body
+fn infer_request(request: &InferenceRequest) -> KserveInferRequest {
+    let mut inputs = vec![KserveTensor::new("text_input", "BYTES", vec![json!(request.prompt)])];
+
+    for (name, datatype, value) in sampling_inputs(&request.sampling) {
+        inputs.push(KserveTensor::new(name, datatype, vec![value]));
+    }
+
+    for (name, words) in [("stop_words", &request.sampling.stop_sequences), ("bad_words", &request.sampling.bad_words)] {
+        if !words.is_empty() {
+            inputs.push(KserveTensor::new(name, "BYTES", words.iter().map(|s| json!(s)).collect()));
+        }
+    }
+
+    inputs.push(KserveTensor::new("stream", "BOOL", vec![json!(false)]));
+
+    KserveInferRequest {
+        id: request.request_id.clone(),
+        inputs,
+        outputs: vec![KserveRequestedOutput { name: "text_output" }],
+    }
+}
+
+/// Build a generate-extension body, where each input is a top-level field
+fn generate_request(request: &InferenceRequest, stream: bool) -> Value {
+    let mut body = Map::new();
+    body.insert("text_input".into(), json!(request.prompt));
+
+    for (name, _, value) in sampling_inputs(&request.sampling) {
+        body.insert(name.into(), value);
+    }
+
+    if !request.sampling.stop_sequences.is_empty() {
+        body.insert("stop_words".into(), json!(request.sampling.stop_sequences));
+    }
+    if !request.sampling.bad_words.is_empty() {
+        body.insert("bad_words".into(), json!(request.sampling.bad_words));
+    }
+
+    body.insert("stream".into(), json!(stream));
+    Value::Object(body)
+}
+
+/// Append a `finished` chunk once `chunks` ends, unless it yielded an error
+fn with_finished_chunk(chunks: impl Stream<Item = Result<InferenceChunk>>) -> impl Stream<Item = Result<InferenceChunk>> {
+    // `None` marks the end of the inner stream
+    let events = chunks.map(Some).chain(stream::once(future::ready(None)));
+
+    events.scan(false, |failed, event| future::ready(match event {
+        Some(chunk) => {
+            *failed |= chunk.is_err();
+            Some(chunk)
+        }
+        None => (!*failed).then(|| Ok(InferenceChunk {
+            text_delta: String::new(),
+            finished: true,
+            finish_reason: Some(FINISH_UNKNOWN.to_string()),
+            usage: None,
+            logprobs: None,
+        })),
+    }))
+}
+
+/// Convert one SSE payload into a chunk
+fn parse_stream_event(data: String) -> Result<InferenceChunk> {
+    let event: TritonGenerateResponse = serde_json::from_str(&data)
+        .map_err(|e| AxonError::InferenceFailed(format!("Invalid stream event: {}", e)))?;
+
+    Ok(InferenceChunk {
+        text_delta: event.text_output,
+        finished: false,
+        finish_reason: None,
+        usage: None,
+        logprobs: None,
+    })
+}
+
+/// KServe v2 inference request
+#[derive(Debug, Serialize)]
+struct KserveInferRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    inputs: Vec<KserveTensor>,
+    outputs: Vec<KserveRequestedOutput>,
+}
+
+/// A tensor in KServe v2 JSON form, batched as `[1, n]`
+#[derive(Debug, Serialize, Deserialize)]
+struct KserveTensor {
+    name: String,
+    datatype: String,
+    shape: Vec<usize>,
+    data: Vec<Value>,
+}
+
+impl KserveTensor {
+    fn new(name: &str, datatype: &str, data: Vec<Value>) -> Self {
+        Self {
+            name: name.to_string(),
+            datatype: datatype.to_string(),
+            shape: vec![1, data.len()],
+            data,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct KserveRequestedOutput {
+    name: &'static str,
+}
+
+/// KServe v2 inference response
+#[derive(Debug, Deserialize)]
+struct KserveInferResponse {
+    outputs: Vec<KserveTensor>,
+}
+
+impl KserveInferResponse {
+    /// Extract the generated text from the `text_output` tensor
+    fn text_output(self) -> Result<String> {
+        let output = self.outputs.into_iter()
+            .find(|o| o.name == "text_output")
+            .ok_or_else(|| AxonError::InferenceFailed("No text_output in response".into()))?;
+
+        output.data.into_iter().next()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .ok_or_else(|| AxonError::InferenceFailed("text_output is empty".into()))
+    }
+}
+
+/// Generate extension response (also each streamed event)
+#[derive(Debug, Deserialize)]
+struct TritonGenerateResponse {
+    text_output: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infer_request_tensors() {
+        let request = InferenceRequest {
+            prompt: "Hello".to_string(),
+            sampling: SamplingParams {
+                temperature: 0.7,
+                stop_sequences: vec!["\n".to_string(), "###".to_string()],
+                ..Default::default()
+            },
+            request_id: Some("req-1".to_string()),
+            ..Default::default()
+        };
+
+        let json = serde_json::to_value(infer_request(&request)).unwrap();
+        assert_eq!(json["id"], "req-1");
+        assert_eq!(json["inputs"][0]["name"], "text_input");
+        assert_eq!(json["inputs"][0]["shape"], json!([1, 1]));
+        assert_eq!(json["inputs"][0]["data"][0], "Hello");
+
+        let inputs = json["inputs"].as_array().unwrap();
+        let stop = inputs.iter().find(|i| i["name"] == "stop_words").unwrap();
+        assert_eq!(stop["shape"], json!([1, 2]));
+        assert_eq!(json["outputs"][0]["name"], "text_output");
+    }
+
+    #[test]
+    fn test_greedy_maps_to_top_k() {
+        let request = InferenceRequest {
+            sampling: SamplingParams {
+                temperature: 0.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let body = generate_request(&request, true);
+        assert_eq!(body["top_k"], 1);
+        assert!(body.get("temperature").is_none());
+        assert_eq!(body["stream"], true);
+    }
+
+    #[test]
+    fn test_extended_sampling_mapped_or_rejected() {
+        let mut sampling = SamplingParams {
+            seed: Some(7),
+            min_tokens: Some(3),
+            bad_words: vec!["darn".to_string()],
+            ..Default::default()
+        };
+        let request = InferenceRequest {
+            sampling: sampling.clone(),
+            ..Default::default()
+        };
+
+        let body = generate_request(&request, false);
+        assert_eq!(body["random_seed"], 7);
+        assert_eq!(body["min_length"], 3);
+        assert_eq!(body["bad_words"][0], "darn");
+        assert!(sampling.check_supported("Triton", SUPPORTED_OPTIONS).is_ok());
+
+        sampling.logit_bias.insert(1, 5.0);
+        assert!(matches!(sampling.check_supported("Triton", SUPPORTED_OPTIONS), Err(AxonError::InvalidConfig(_))));
+
+        let routed = InferenceRequest {
+            model: Some("sql-lora".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(check_request(&routed), Err(AxonError::InvalidConfig(_))));
+    }
+
+    #[tokio::test]
+    async fn test_finished_chunk_only_after_clean_end() {
+        let chunk = |text: &str| Ok(parse_stream_event(format!(r#"{{"text_output":"{}"}}"#, text)).unwrap());
+
+        let clean: Vec<_> = with_finished_chunk(stream::iter(vec![chunk("Hi")])).collect().await;
+        assert_eq!(clean.len(), 2);
+        let last = clean[1].as_ref().unwrap();
+        assert!(last.finished);
+        assert_eq!(last.finish_reason.as_deref(), Some("unknown"));
+
+        let failed = vec![chunk("Hi"), Err(AxonError::InferenceFailed("connection reset".into()))];
+        let failed: Vec<_> = with_finished_chunk(stream::iter(failed)).collect().await;
+        assert_eq!(failed.len(), 2);
+        assert!(failed[1].is_err());
+    }
+
+    #[test]
+    fn test_infer_response_text_output() {
+        let resp: KserveInferResponse = serde_json::from_str(
+            r#"{"model_name":"ensemble","model_version":"1","outputs":[{"name":"text_output","datatype":"BYTES","shape":[1,1],"data":["Hi there"]}]}"#,
+        )
+        .unwrap();
+        assert_eq!(resp.text_output().unwrap(), "Hi there");
+    }
+}
@@ -0,0 +1,103 @@
+//! Triton-specific configuration
+
+use crate::error::{AxonError, Result};
+use crate::types::ModelConfig;
+
+/// Which Triton HTTP endpoint family requests are sent to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TritonProtocol {
+    /// KServe v2 tensor protocol (`/v2/models/{name}/infer`)
+    Infer,
+    /// Triton's JSON generate extension (`/v2/models/{name}/generate`)
+    #[default]
+    Generate,
+}
+
+/// Triton-specific configuration derived from ModelConfig
+#[derive(Debug, Clone)]
+pub struct TritonConfig {
+    /// Name of the Triton model to call (e.g., `ensemble` or `tensorrt_llm_bls`)
+    pub model_name: String,
+
+    /// Specific model version, or the server's version policy if unset
+    pub model_version: Option<String>,
+
+    /// Endpoint family used for inference
+    pub protocol: TritonProtocol,
+}
+
+impl TritonConfig {
+    /// Create Triton config from generic ModelConfig
+    ///
+    /// Recognized `extra_options`:
+    /// - `protocol`: `infer` or `generate` (default)
+    /// - `model_version`: version to pin requests to
+    pub fn from_model_config(config: ModelConfig) -> Result<Self> {
+        let mut protocol = TritonProtocol::default();
+        let mut model_version = None;
+
+        for (key, value) in &config.extra_options {
+            match key.as_str() {
+                "protocol" => {
+                    protocol = match value.as_str() {
+                        "infer" => TritonProtocol::Infer,
+                        "generate" => TritonProtocol::Generate,
+                        other => {
+                            return Err(AxonError::InvalidConfig(format!(
+                                "unknown Triton protocol '{}'", other
+                            )));
+                        }
+                    }
+                }
+                "model_version" => model_version = Some(value.clone()),
+                _ => {}
+            }
+        }
+
+        Ok(Self {
+            model_name: config.model_name,
+            model_version,
+            protocol,
+        })
+    }
+
+    /// Path prefix for this model's endpoints
+    pub fn model_path(&self) -> String {
+        match &self.model_version {
+            Some(version) => format!("/v2/models/{}/versions/{}", self.model_name, version),
+            None => format!("/v2/models/{}", self.model_name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_model_config() {
+        let config = TritonConfig::from_model_config(ModelConfig {
+            model_name: "tensorrt_llm_bls".to_string(),
+            extra_options: vec![
+                ("protocol".to_string(), "infer".to_string()),
+                ("model_version".to_string(), "2".to_string()),
+            ],
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(config.protocol, TritonProtocol::Infer);
+        assert_eq!(config.model_path(), "/v2/models/tensorrt_llm_bls/versions/2");
+    }
+
+    #[test]
+    fn test_unknown_protocol_rejected() {
+        let result = TritonConfig::from_model_config(ModelConfig {
+            model_name: "ensemble".to_string(),
+            extra_options: vec![("protocol".to_string(), "grpc".to_string())],
+            ..Default::default()
+        });
+
+        assert!(matches!(result, Err(AxonError::InvalidConfig(_))));
+    }
+}
@@ -6,19 +6,34 @@
 pub mod process;
 pub mod client;
 pub mod config;
+pub mod launcher;
+pub mod metrics;
+pub mod pidfile;
+pub mod startup;
+pub mod supervisor;
 
-use crate::backend::{BackendMetrics, HealthStatus, InferenceBackend};
+use crate::backend::{BackendMetrics, HealthStatus, InferenceBackend, InferenceStream};
 use crate::error::{AxonError, Result};
-use crate::types::{InferenceRequest, InferenceResponse, ModelConfig};
-
-use process::VllmProcess;
-use client::VllmClient;
+use crate::deadline::RequestLimits;
+use crate::metrics::MetricsTracker;
+use crate::retry::{Retrier, RetryPolicy};
+use crate::types::{
+    BatchInferenceRequest, BatchInferenceResponse, ChatRequest, InferenceRequest, InferenceResponse, ModelConfig,
+};
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
+
+use client::{ServedModel, VllmClient};
 use config::VllmConfig;
+use metrics::VllmEngineMetrics;
+use pidfile::{EngineRecord, OrphanPolicy};
+use supervisor::{LifecycleEvent, Supervisor, SupervisorState};
 
 /// vLLM backend for Axon
 ///
 /// Spawns and manages a vLLM server process, communicating via its
-/// OpenAI-compatible HTTP API.
+/// OpenAI-compatible HTTP API. A spawned server is supervised and restarted
+/// according to `VllmConfig::restart_policy`.
 ///
 /// # Example
 ///
@@ -44,8 +59,11 @@ use config::VllmConfig;
 /// # }
 /// ```
 pub struct VllmBackend {
-    /// The vLLM process (if spawned by Axon)
-    process: Option<VllmProcess>,
+    /// Supervisor of the vLLM process (if spawned by Axon)
+    supervisor: Option<Supervisor>,
+
+    /// Engine left by a previous run and adopted instead of spawning
+    adopted: Option<EngineRecord>,
 
     /// HTTP client for communicating with vLLM API
     client: Option<VllmClient>,
@@ -53,22 +71,39 @@ pub struct VllmBackend {
     /// Whether this backend spawned its own vLLM process
     owns_process: bool,
 
-    /// Current model configuration
+    /// Name the loaded model is served under
     current_model: Option<String>,
 
-    /// Metrics tracker
-    metrics: BackendMetrics,
+    /// Models and LoRA adapters listed by the server's `/v1/models`
+    served_models: Mutex<Vec<ServedModel>>,
+
+    /// Metrics tracker, shared with in-flight streams
+    metrics: Arc<MetricsTracker>,
+
+    /// Last engine-side metrics scraped from vLLM
+    engine_metrics: Mutex<Option<VllmEngineMetrics>>,
+
+    /// Lifecycle events from the supervisor
+    events: broadcast::Sender<LifecycleEvent>,
+
+    /// Retries of requests that fail transiently
+    retrier: Retrier,
 }
 
 impl VllmBackend {
     /// Create a new vLLM backend that will spawn its own process
     pub fn new() -> Self {
         Self {
-            process: None,
+            supervisor: None,
+            adopted: None,
             client: None,
             owns_process: true,
             current_model: None,
-            metrics: BackendMetrics::new(),
+            served_models: Mutex::new(Vec::new()),
+            metrics: MetricsTracker::new(),
+            engine_metrics: Mutex::new(None),
+            events: broadcast::channel(64).0,
+            retrier: Retrier::new(RetryPolicy::default()),
         }
     }
 
@@ -81,23 +116,226 @@ impl VllmBackend {
     /// * `base_url` - The base URL of the running vLLM server (e.g., "http://localhost:8000")
     pub fn connect_to(base_url: String) -> Self {
         Self {
-            process: None,
+            supervisor: None,
+            adopted: None,
             client: Some(VllmClient::new(base_url)),
             owns_process: false,
             current_model: None,
-            metrics: BackendMetrics::new(),
+            served_models: Mutex::new(Vec::new()),
+            metrics: MetricsTracker::new(),
+            engine_metrics: Mutex::new(None),
+            events: broadcast::channel(64).0,
+            retrier: Retrier::new(RetryPolicy::default()),
         }
     }
 
+    /// Load a model using vLLM-specific configuration
+    ///
+    /// `load_model` converts a generic `ModelConfig` and calls this; use it
+    /// directly to set options `ModelConfig` cannot express, such as log sinks.
+    pub async fn load_with_config(&mut self, config: VllmConfig) -> Result<()> {
+        // Validate configuration
+        if config.model_name.is_empty() {
+            return Err(AxonError::InvalidConfig("model_name cannot be empty".into()));
+        }
+
+        let model_name = config.served_model_name.clone()
+            .unwrap_or_else(|| config.model_name.clone());
+
+        // If we own the process, spawn vLLM and supervise it
+        if self.owns_process && !self.take_over_orphan(&config).await? {
+            let supervisor = Supervisor::start(config, self.events.clone()).await?;
+
+            self.client = Some(VllmClient::new(supervisor.base_url()));
+            self.supervisor = Some(supervisor);
+        }
+
+        // Verify the server is responding and serves the model
+        if let Some(client) = self.client() {
+            client.health_check().await?;
+        }
+        let models = self.refresh_models().await?;
+        if !models.iter().any(|m| m.id == model_name) {
+            let served: Vec<_> = models.iter().map(|m| m.id.as_str()).collect();
+            return Err(AxonError::ModelLoadFailed(format!(
+                "server does not serve '{}' (serving: {})",
+                model_name,
+                served.join(", ")
+            )));
+        }
+
+        self.current_model = Some(model_name);
+        Ok(())
+    }
+
+    /// Apply `orphan_policy` to an engine recorded in `state_dir` by a
+    /// previous run, returning `true` if it was adopted
+    async fn take_over_orphan(&mut self, config: &VllmConfig) -> Result<bool> {
+        let Some(orphan) = EngineRecord::find_orphan(config)? else {
+            return Ok(false);
+        };
+
+        match config.orphan_policy {
+            OrphanPolicy::Adopt => {
+                if !orphan.matches(config) {
+                    // Started with other settings; replace it with a fresh engine
+                    tracing::info!(pid = orphan.pid, "orphaned vLLM was started with a different command; terminating it");
+                    orphan.terminate(process::TERMINATE_GRACE).await;
+                    return Ok(false);
+                }
+
+                let client = VllmClient::new(orphan.base_url());
+                if client.health_check().await.is_ok() {
+                    // Claim it, so other backends don't take it for an orphan
+                    let mut orphan = orphan;
+                    orphan.claim();
+                    orphan.write()?;
+
+                    self.client = Some(client);
+                    self.adopted = Some(orphan);
+                    return Ok(true);
+                }
+
+                // Alive but not serving; replace it with a fresh engine
+                orphan.terminate(process::TERMINATE_GRACE).await;
+            }
+            OrphanPolicy::Terminate => orphan.terminate(process::TERMINATE_GRACE).await,
+            OrphanPolicy::Ignore => {}
+        }
+
+        Ok(false)
+    }
+
+    /// Base URL of the server requests are sent to
+    ///
+    /// For a spawned server this reflects the port actually chosen, which
+    /// matters when `VllmConfig::port` is `None`.
+    pub fn base_url(&self) -> Option<&str> {
+        self.client().map(VllmClient::base_url)
+    }
+
+    /// Set how requests that fail transiently are retried
+    ///
+    /// Defaults to `RetryPolicy::default()`; use `RetryPolicy::none()` to
+    /// disable retries.
+    pub fn set_retry_policy(&mut self, policy: RetryPolicy) {
+        self.retrier = Retrier::new(policy);
+    }
+
+    /// Fetch the served models and LoRA adapters from `/v1/models` and cache them
+    pub async fn refresh_models(&self) -> Result<Vec<ServedModel>> {
+        let client = self.client().ok_or(AxonError::BackendNotRunning)?;
+        let models = client.list_models().await?;
+
+        *self.served_models.lock().unwrap() = models.clone();
+        Ok(models)
+    }
+
+    /// Models and LoRA adapters served, as of the last refresh
+    pub fn served_models(&self) -> Vec<ServedModel> {
+        self.served_models.lock().unwrap().clone()
+    }
+
+    /// Metadata of the model requests go to unless they name another
+    ///
+    /// This is the loaded model, or for a server connected to without
+    /// `load_model`, the first base model it serves. Answers from the cache,
+    /// so on a `connect_to` backend it is `None` until `refresh_models` or
+    /// the first request has discovered the served models; call
+    /// `refresh_models` first to read it up front.
+    pub fn model_info(&self) -> Option<ServedModel> {
+        let models = self.served_models.lock().unwrap();
+        let current = self.current_model.as_deref();
+
+        models.iter()
+            .find(|m| Some(m.id.as_str()) == current)
+            .or_else(|| models.iter().find(|m| !m.is_adapter()))
+            .cloned()
+    }
+
+    /// Context length of the default model, if known
+    ///
+    /// Like `model_info`, this needs a prior `refresh_models` on a
+    /// `connect_to` backend.
+    pub fn max_model_len(&self) -> Option<usize> {
+        self.model_info().and_then(|m| m.max_model_len)
+    }
+
+    /// Model name to send for a request that asked for `requested`
+    async fn model_for(&self, requested: Option<&str>) -> Result<String> {
+        if let Some(model) = requested.or(self.current_model.as_deref()) {
+            return Ok(model.to_string());
+        }
+
+        // Connected without `load_model`; discover what the server serves
+        if self.served_models.lock().unwrap().is_empty() {
+            self.refresh_models().await?;
+        }
+        self.model_info()
+            .map(|m| m.id)
+            .ok_or_else(|| AxonError::BackendError("vLLM server lists no models".into()))
+    }
+
+    /// Subscribe to lifecycle events of the spawned server
+    ///
+    /// Events are only sent for servers spawned by this backend. Subscribe
+    /// before `load_model` to observe the initial start.
+    pub fn subscribe(&self) -> broadcast::Receiver<LifecycleEvent> {
+        self.events.subscribe()
+    }
+
+    /// Scrape vLLM's `/metrics` endpoint and cache the result
+    ///
+    /// The cached values are included in `metrics()`. `health_check()` also
+    /// refreshes them, so callers polling health need not call this directly.
+    pub async fn refresh_engine_metrics(&self) -> Result<VllmEngineMetrics> {
+        let client = self.client().ok_or(AxonError::BackendNotRunning)?;
+        let engine = client.scrape_metrics().await?;
+
+        *self.engine_metrics.lock().unwrap() = Some(engine);
+        Ok(engine)
+    }
+
     /// Get a reference to the HTTP client
     fn client(&self) -> Option<&VllmClient> {
         self.client.as_ref()
     }
 
+    /// Get the HTTP client, failing if the server is not available
+    async fn ready_client(&self) -> Result<&VllmClient> {
+        let client = self.client.as_ref()
+            .ok_or(AxonError::BackendNotRunning)?;
+
+        // Check process health if we own it
+        if self.owns_process && !self.check_process().await? {
+            return Err(AxonError::BackendNotRunning);
+        }
+
+        Ok(client)
+    }
+
+    /// Run `op`, retrying transient failures
+    ///
+    /// A backend that was never loaded fails immediately rather than waiting
+    /// out the backoff.
+    async fn with_retries<T, F, Fut>(&self, op: F) -> Result<(T, u32)>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        if self.client.is_none() {
+            return Err(AxonError::BackendNotRunning);
+        }
+
+        self.retrier.run(op).await
+    }
+
     /// Check if the process is still running
     async fn check_process(&self) -> Result<bool> {
-        if let Some(process) = &self.process {
-            Ok(process.is_running())
+        if let Some(supervisor) = &self.supervisor {
+            Ok(supervisor.state() == SupervisorState::Running)
+        } else if let Some(engine) = &self.adopted {
+            Ok(engine.is_alive())
         } else {
             Ok(false)
         }
@@ -112,59 +350,85 @@ impl Default for VllmBackend {
 
 impl InferenceBackend for VllmBackend {
     async fn load_model(&mut self, config: ModelConfig) -> Result<()> {
-        // Validate configuration
-        if config.model_name.is_empty() {
-            return Err(AxonError::InvalidConfig("model_name cannot be empty".into()));
-        }
-
-        // If we own the process, spawn vLLM
-        if self.owns_process {
-            let vllm_config = VllmConfig::from_model_config(config.clone());
-            let process = VllmProcess::spawn(vllm_config).await?;
-
-            // Wait for vLLM to be ready
-            process.wait_until_ready().await?;
-
-            let base_url = format!("http://{}:{}", config.host.as_deref().unwrap_or("127.0.0.1"), config.port.unwrap_or(8000));
-            self.client = Some(VllmClient::new(base_url));
-            self.process = Some(process);
-        }
-
-        // Verify the server is responding
-        if let Some(client) = self.client() {
-            client.health_check().await?;
-        }
-
-        self.current_model = Some(config.model_name);
-        Ok(())
+        self.load_with_config(VllmConfig::from_model_config(config)?).await
     }
 
     async fn infer(&self, request: InferenceRequest) -> Result<InferenceResponse> {
-        let client = self.client.as_ref()
-            .ok_or_else(|| AxonError::BackendNotRunning)?;
+        let limits = RequestLimits::new(request.timeout, request.cancel.as_ref());
+        let attempt = || async {
+            let client = self.ready_client().await?;
+            client.infer(&self.model_for(request.model.as_deref()).await?, request.clone()).await
+        };
+        self.metrics.start().run(async {
+            let (response, attempts) = limits.run(self.with_retries(attempt)).await?;
+            Ok(InferenceResponse { attempts, ..response })
+        }).await
+    }
 
-        // Check process health if we own it
-        if self.owns_process && !self.check_process().await? {
-            return Err(AxonError::BackendNotRunning);
-        }
+    async fn infer_stream(&self, request: InferenceRequest) -> Result<InferenceStream> {
+        let limits = RequestLimits::new(request.timeout, request.cancel.as_ref());
+        // Retries happen before the stream is returned, so no tokens have
+        // been emitted yet
+        let attempt = || async {
+            let client = self.ready_client().await?;
+            client.infer_stream(&self.model_for(request.model.as_deref()).await?, request.clone()).await
+        };
+        self.metrics.start().run_stream(limits.run_stream(async {
+            let (stream, _) = self.with_retries(attempt).await?;
+            Ok(stream)
+        })).await
+    }
 
-        client.infer(request).await
+    async fn chat(&self, request: ChatRequest) -> Result<InferenceResponse> {
+        let limits = RequestLimits::new(request.timeout, request.cancel.as_ref());
+        let attempt = || async {
+            let client = self.ready_client().await?;
+            client.chat(&self.model_for(request.model.as_deref()).await?, request.clone()).await
+        };
+        self.metrics.start().run(async {
+            let (response, attempts) = limits.run(self.with_retries(attempt)).await?;
+            Ok(InferenceResponse { attempts, ..response })
+        }).await
+    }
+
+    async fn infer_batch(&self, request: BatchInferenceRequest) -> Result<BatchInferenceResponse> {
+        let limits = RequestLimits::new(request.timeout, request.cancel.as_ref());
+        let attempt = || async {
+            let client = self.ready_client().await?;
+            client.infer_batch(&self.model_for(request.model.as_deref()).await?, request.clone()).await
+        };
+        self.metrics.start().run(async {
+            let (response, attempts) = limits.run(self.with_retries(attempt)).await?;
+            Ok(BatchInferenceResponse { attempts, ..response })
+        }).await
     }
 
     async fn health_check(&self) -> HealthStatus {
-        // If we own the process, check if it's running
-        if self.owns_process {
-            if let Some(process) = &self.process {
-                if !process.is_running() {
-                    return HealthStatus::Failed;
-                }
+        // If we own the process, check what the supervisor knows
+        if self.owns_process
+            && let Some(supervisor) = &self.supervisor
+        {
+            match supervisor.state() {
+                SupervisorState::Running => {}
+                SupervisorState::Restarting => return HealthStatus::Starting,
+                SupervisorState::Failed(exit) => return HealthStatus::Failed(Some(exit)),
+                SupervisorState::Stopped => return HealthStatus::Failed(None),
             }
         }
+        if let Some(engine) = &self.adopted
+            && !engine.is_alive()
+        {
+            return HealthStatus::Failed(None);
+        }
 
         // Check the HTTP API
         if let Some(client) = self.client() {
             match client.health_check().await {
-                Ok(_) => HealthStatus::Healthy,
+                Ok(_) => {
+                    // Best-effort: older servers may not expose /metrics
+                    let _ = self.refresh_engine_metrics().await;
+                    HealthStatus::Healthy
+                }
                 Err(_) => HealthStatus::Degraded,
             }
         } else {
@@ -173,21 +437,42 @@ impl InferenceBackend for VllmBackend {
     }
 
     fn metrics(&self) -> BackendMetrics {
-        self.metrics.clone()
+        let mut metrics = self.metrics.snapshot();
+        if let Some(engine) = *self.engine_metrics.lock().unwrap() {
+            engine.apply_to(&mut metrics);
+        }
+        metrics
     }
 
     async fn shutdown(&mut self) -> Result<()> {
         // Shutdown the process if we own it
-        if let Some(process) = self.process.take() {
-            process.terminate().await?;
+        if let Some(supervisor) = self.supervisor.take() {
+            supervisor.shutdown().await?;
+        }
+        if let Some(engine) = self.adopted.take() {
+            engine.terminate(process::TERMINATE_GRACE).await;
+            engine.remove();
         }
 
         self.client = None;
         self.current_model = None;
+        self.served_models.lock().unwrap().clear();
+        *self.engine_metrics.lock().unwrap() = None;
         Ok(())
     }
 }
 
+impl Drop for VllmBackend {
+    fn drop(&mut self) {
+        // A spawned engine is killed when its `VllmProcess` drops; an adopted
+        // one is not our child, so kill it here the same way
+        if let Some(engine) = self.adopted.take() {
+            engine.kill();
+            engine.remove();
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -204,6 +489,7 @@ mod tests {
         let backend = VllmBackend::connect_to("http://localhost:8000".to_string());
         assert!(!backend.owns_process);
         assert!(backend.client.is_some());
+        assert_eq!(backend.base_url(), Some("http://localhost:8000"));
     }
 
     #[test]
@@ -217,4 +503,142 @@ mod tests {
         let backend = VllmBackend::new();
         assert_eq!(backend.health_check().await, HealthStatus::Starting);
     }
+
+    #[test]
+    fn test_dropped_backend_kills_adopted_engine() {
+        use std::os::unix::process::CommandExt;
+
+        let mut engine = std::process::Command::new("sleep").arg("30").process_group(0).spawn().unwrap();
+        let mut backend = VllmBackend::new();
+        backend.adopted = Some(EngineRecord {
+            pid: engine.id(),
+            host: "127.0.0.1".to_string(),
+            port: 8000,
+            command: vec!["sleep".to_string(), "30".to_string()],
+            owner_pid: std::process::id(),
+            owner_start: None,
+            path: std::env::temp_dir().join(format!("axon-adopted-{}.json", std::process::id())),
+        });
+
+        drop(backend);
+        let status = engine.wait().unwrap();
+        assert_eq!(std::os::unix::process::ExitStatusExt::signal(&status), Some(libc::SIGKILL));
+    }
+
+    #[tokio::test]
+    async fn test_failed_requests_are_counted() {
+        let backend = VllmBackend::new();
+        assert!(backend.infer(InferenceRequest::default()).await.is_err());
+
+        let metrics = backend.metrics();
+        assert_eq!(metrics.total_requests, 1);
+        assert_eq!(metrics.failed_requests, 1);
+        assert_eq!(metrics.pending_requests, 0);
+    }
+
+    /// Serve canned JSON bodies by request path until the test ends
+    async fn mock_server(routes: Vec<(&'static str, &'static str)>) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match socket.read(&mut buf).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => request.extend_from_slice(&buf[..n]),
+                    }
+                }
+                let request = String::from_utf8_lossy(&request);
+                let path = request.split_whitespace().nth(1).unwrap_or_default();
+                let (status, body) = match routes.iter().find(|(route, _)| *route == path) {
+                    Some((_, body)) => ("200 OK", *body),
+                    None => ("404 Not Found", ""),
+                };
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_served_models_are_discovered() {
+        let url = mock_server(vec![
+            ("/health", ""),
+            ("/v1/models", r#"{"object":"list","data":[
+                {"id":"sql-lora","root":"/adapters/sql","parent":"llama"},
+                {"id":"llama","root":"meta-llama/Llama-3.1-8B","max_model_len":8192}
+            ]}"#),
+        ]).await;
+
+        let mut backend = VllmBackend::connect_to(url);
+        assert_eq!(backend.model_for(None).await.unwrap(), "llama");
+        assert_eq!(backend.model_for(Some("sql-lora")).await.unwrap(), "sql-lora");
+        assert_eq!(backend.max_model_len(), Some(8192));
+
+        let err = backend.load_model(ModelConfig {
+            model_name: "mistral".to_string(),
+            ..Default::default()
+        }).await.unwrap_err();
+        assert!(err.to_string().contains("serving: sql-lora, llama"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_infer_batch() {
+        let url = mock_server(vec![
+            ("/v1/models", r#"{"object":"list","data":[{"id":"llama"}]}"#),
+            ("/v1/completions", r#"{"choices":[
+                {"index":1,"text":"a1","finish_reason":"stop"},
+                {"index":2,"text":"b0","finish_reason":"stop"},
+                {"index":0,"text":"a0","finish_reason":"length"},
+                {"index":3,"text":"b1","finish_reason":"stop"}],
+                "usage":{"prompt_tokens":4,"completion_tokens":12,"total_tokens":16}}"#),
+        ]).await;
+
+        let backend = VllmBackend::connect_to(url);
+        let response = backend.infer_batch(BatchInferenceRequest {
+            prompts: vec!["a".to_string(), "b".to_string()],
+            sampling: crate::SamplingParams { n: 2, ..Default::default() },
+            ..Default::default()
+        }).await.unwrap();
+
+        let texts: Vec<&str> = response.choices.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(texts, ["a0", "a1", "b0", "b1"]);
+        assert_eq!(response.for_prompt(1).map(|c| c.index).collect::<Vec<_>>(), [0, 1]);
+        assert_eq!(response.usage.completion_tokens, 12);
+        assert_eq!(backend.metrics().total_requests, 1);
+    }
+
+    #[tokio::test]
+    async fn test_timeout_closes_connection() {
+        use tokio::io::AsyncReadExt;
+
+        // A server that accepts the request but never answers
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 4096];
+            while socket.read(&mut buf).await.unwrap() > 0 {}
+        });
+
+        let backend = VllmBackend::connect_to(url);
+        let result = backend.infer(InferenceRequest {
+            timeout: Some(std::time::Duration::from_millis(100)),
+            ..Default::default()
+        }).await;
+        assert!(matches!(result, Err(AxonError::Timeout(_))));
+
+        // The server sees EOF once the abandoned request is dropped
+        tokio::time::timeout(std::time::Duration::from_secs(5), server).await.unwrap().unwrap();
+    }
 }
@@ -1,8 +1,19 @@
 //! HTTP client for vLLM's OpenAI-compatible API
 
-use crate::error::{AxonError, Result};
-use crate::types::{InferenceRequest, InferenceResponse};
+use crate::backend::InferenceStream;
+use crate::deadline::{CONNECT_TIMEOUT, CONTROL_TIMEOUT};
+use crate::error::{check_status, ApiError, AxonError, Result};
+use crate::openai::{OpenAiChatResponse, OpenAiMessage, OpenAiTool, OpenAiToolChoice, OpenAiUsage};
+use crate::sse;
+use crate::types::{
+    BatchInferenceRequest, BatchInferenceResponse, ChatRequest, CompletionChoice, InferenceChunk, InferenceRequest,
+    InferenceResponse, SamplingParams, TokenLogprob, TopLogprob, Usage,
+};
+use futures_util::{future, StreamExt};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+
+use super::metrics::VllmEngineMetrics;
 
 /// HTTP client for communicating with vLLM
 pub struct VllmClient {
@@ -16,18 +27,24 @@ pub struct VllmClient {
 impl VllmClient {
     /// Create a new vLLM client
     pub fn new(base_url: String) -> Self {
+        // Inference calls are bounded per request by `RequestLimits`
         let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(120))
+            .connect_timeout(CONNECT_TIMEOUT)
             .build()
             .unwrap();
 
         Self { base_url, client }
     }
 
+    /// Base URL of the vLLM server
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
     /// Check if the vLLM server is healthy
     pub async fn health_check(&self) -> Result<()> {
         let url = format!("{}/health", self.base_url);
-        let resp = self.client.get(&url).send().await?;
+        let resp = self.client.get(&url).timeout(CONTROL_TIMEOUT).send().await?;
 
         if resp.status().is_success() {
             Ok(())
@@ -36,61 +53,235 @@ impl VllmClient {
         }
     }
 
-    /// Run inference on a single prompt
-    pub async fn infer(&self, request: InferenceRequest) -> Result<InferenceResponse> {
-        let url = format!("{}/v1/completions", self.base_url);
-
-        let vllm_req = VllmCompletionRequest {
-            model: "default".to_string(),
-            prompt: request.prompt.clone(),
-            max_tokens: request.sampling.max_tokens,
-            temperature: request.sampling.temperature,
-            top_p: request.sampling.top_p,
-            top_k: request.sampling.top_k,
-            presence_penalty: request.sampling.presence_penalty,
-            frequency_penalty: request.sampling.frequency_penalty,
-            stop: if request.sampling.stop_sequences.is_empty() {
-                None
-            } else {
-                Some(request.sampling.stop_sequences)
-            },
-        };
+    /// Scrape vLLM's Prometheus `/metrics` endpoint
+    pub async fn scrape_metrics(&self) -> Result<VllmEngineMetrics> {
+        let url = format!("{}/metrics", self.base_url);
+        let resp = check_status(self.client.get(&url).timeout(CONTROL_TIMEOUT).send().await?).await?;
+
+        Ok(VllmEngineMetrics::from_prometheus(&resp.text().await?))
+    }
+
+    /// List the models and LoRA adapters the server serves
+    pub async fn list_models(&self) -> Result<Vec<ServedModel>> {
+        let url = format!("{}/v1/models", self.base_url);
+        let resp = check_status(self.client.get(&url).timeout(CONTROL_TIMEOUT).send().await?).await?;
+        let list: VllmModelList = resp.json().await?;
+
+        Ok(list.data)
+    }
+
+    /// Run inference on a single prompt with the served model `model`
+    pub async fn infer(&self, model: &str, request: InferenceRequest) -> Result<InferenceResponse> {
+        check_single(&request.sampling)?;
+        let vllm_req = VllmCompletionRequest::new(model, &request, false);
 
         let start = std::time::Instant::now();
-        let resp = self.client.post(&url).json(&vllm_req).send().await?;
+        let resp = self.post("/v1/completions", &vllm_req).await?;
         let elapsed = start.elapsed();
 
-        if !resp.status().is_success() {
-            let status = resp.status();
-            let text = resp.text().await.unwrap_or_default();
-            return Err(AxonError::InferenceFailed(format!("{}: {}", status, text)));
+        let vllm_resp: VllmCompletionResponse = resp.json().await?;
+
+        let choice = vllm_resp.choices.into_iter().next()
+            .ok_or_else(|| AxonError::InferenceFailed("No choices in response".into()))?
+            .into_choice(&request.sampling)?;
+        let usage = Usage::from(vllm_resp.usage);
+
+        Ok(InferenceResponse {
+            text: choice.text,
+            tokens_generated: usage.completion_tokens,
+            usage,
+            inference_time: elapsed.as_secs_f64(),
+            tokens_per_second: usage.tokens_per_second(elapsed),
+            finish_reason: choice.finish_reason,
+            request_id: request.request_id,
+            attempts: 1,
+            logprobs: choice.logprobs,
+            prompt_logprobs: choice.prompt_logprobs,
+            tool_calls: Vec::new(),
+        })
+    }
+
+    /// Run inference on several prompts in one request, returning `n`
+    /// completions for each
+    pub async fn infer_batch(&self, model: &str, request: BatchInferenceRequest) -> Result<BatchInferenceResponse> {
+        if request.prompts.is_empty() {
+            return Err(AxonError::InvalidConfig("batch has no prompts".into()));
         }
+        check_n(&request.sampling)?;
+        let vllm_req = VllmCompletionRequest::batch(model, &request);
+
+        let start = std::time::Instant::now();
+        let resp = self.post("/v1/completions", &vllm_req).await?;
+        let elapsed = start.elapsed();
 
         let vllm_resp: VllmCompletionResponse = resp.json().await?;
 
-        let choice = vllm_resp.choices.first()
+        let mut choices = vllm_resp.choices.into_iter()
+            .map(|choice| choice.into_choice(&request.sampling))
+            .collect::<Result<Vec<_>>>()?;
+        choices.sort_by_key(|c| (c.prompt_index, c.index));
+        let usage = Usage::from(vllm_resp.usage);
+
+        Ok(BatchInferenceResponse {
+            choices,
+            usage,
+            inference_time: elapsed.as_secs_f64(),
+            tokens_per_second: usage.tokens_per_second(elapsed),
+            request_id: request.request_id,
+            attempts: 1,
+        })
+    }
+
+    /// Run inference on a single prompt, streaming tokens as they are generated
+    ///
+    /// Uses vLLM's server-sent events mode (`stream: true`). The stream ends
+    /// when vLLM sends its `[DONE]` sentinel or closes the connection. Usage
+    /// is requested on every chunk, so the final chunk carries the totals.
+    pub async fn infer_stream(&self, model: &str, request: InferenceRequest) -> Result<InferenceStream> {
+        check_single(&request.sampling)?;
+        if request.sampling.prompt_logprobs.is_some() {
+            return Err(AxonError::InvalidConfig("prompt_logprobs is only supported by infer".into()));
+        }
+        let vllm_req = VllmCompletionRequest::new(model, &request, true);
+        let resp = self.post("/v1/completions", &vllm_req).await?;
+
+        let chunks = sse::events(resp.bytes_stream())
+            .take_while(|event| future::ready(!matches!(event, Ok(data) if data == "[DONE]")))
+            .filter_map(|event| future::ready(event.and_then(parse_stream_event).transpose()));
+
+        Ok(Box::pin(chunks))
+    }
+
+    /// Run a chat completion via `/v1/chat/completions`
+    ///
+    /// vLLM applies the served model's chat template to the messages.
+    pub async fn chat(&self, model: &str, request: ChatRequest) -> Result<InferenceResponse> {
+        if request.sampling.prompt_logprobs.is_some() {
+            return Err(AxonError::InvalidConfig("prompt_logprobs is only supported by infer".into()));
+        }
+        check_single(&request.sampling)?;
+        let vllm_req = VllmChatRequest::new(model, &request);
+
+        let start = std::time::Instant::now();
+        let resp = self.post("/v1/chat/completions", &vllm_req).await?;
+        let elapsed = start.elapsed();
+
+        let vllm_resp: OpenAiChatResponse = resp.json().await?;
+
+        let mut choice = vllm_resp.choices.into_iter().next()
             .ok_or_else(|| AxonError::InferenceFailed("No choices in response".into()))?;
+        let usage = vllm_resp.usage.map(Usage::from).unwrap_or_default();
+        let logprobs = choice.logprobs.map(|l| l.into_tokens(choice.token_ids.as_deref()));
+        let tool_calls = choice.message.take_tool_calls();
 
         Ok(InferenceResponse {
-            text: choice.text.clone(),
-            tokens_generated: choice.text_tokens.unwrap_or(0),
+            text: choice.message.content.unwrap_or_default(),
+            tokens_generated: usage.completion_tokens,
+            usage,
             inference_time: elapsed.as_secs_f64(),
-            tokens_per_second: if elapsed.as_secs_f64() > 0.0 {
-                (choice.text_tokens.unwrap_or(0) as f32) / (elapsed.as_secs_f64() as f32)
-            } else {
-                0.0
-            },
-            finish_reason: choice.finish_reason.clone(),
+            tokens_per_second: usage.tokens_per_second(elapsed),
+            finish_reason: choice.finish_reason.unwrap_or_default(),
             request_id: request.request_id,
+            attempts: 1,
+            logprobs,
+            prompt_logprobs: None,
+            tool_calls,
         })
     }
+
+    /// POST a JSON body, turning non-success statuses into errors
+    async fn post<T: Serialize>(&self, path: &str, body: &T) -> Result<reqwest::Response> {
+        let url = format!("{}{}", self.base_url, path);
+        let resp = self.client.post(&url).json(body).send().await?;
+
+        check_status(resp).await
+    }
 }
 
-/// vLLM completion request format (OpenAI-compatible)
+/// Reject `n` and `best_of` values vLLM would fail on
+fn check_n(sampling: &SamplingParams) -> Result<()> {
+    if sampling.n == 0 {
+        return Err(AxonError::InvalidConfig("n must be at least 1".into()));
+    }
+    if let Some(best_of) = sampling.best_of
+        && best_of < sampling.n
+    {
+        return Err(AxonError::InvalidConfig(format!(
+            "best_of ({}) must be at least n ({})",
+            best_of, sampling.n
+        )));
+    }
+    Ok(())
+}
+
+/// Reject `n > 1`, whose extra completions only `infer_batch` can return
+fn check_single(sampling: &SamplingParams) -> Result<()> {
+    check_n(sampling)?;
+    if sampling.n > 1 {
+        return Err(AxonError::InvalidConfig("n > 1 is only supported by infer_batch".into()));
+    }
+    Ok(())
+}
+
+/// Convert one SSE payload into a chunk, skipping events without choices
+///
+/// A failure after the response started streaming arrives as an
+/// `{"error": {...}}` event and becomes an `ApiError`.
+fn parse_stream_event(data: String) -> Result<Option<InferenceChunk>> {
+    let event: VllmStreamResponse = serde_json::from_str(&data)
+        .map_err(|e| AxonError::InferenceFailed(format!("Invalid stream event: {}", e)))?;
+
+    if let Some(error) = &event.error {
+        let status = error.get("code").and_then(|code| code.as_u64()).unwrap_or(500);
+        return Err(ApiError::from_response(status as u16, &data).into());
+    }
+
+    let usage = event.usage.map(Usage::from);
+    Ok(event.choices.into_iter().next().map(|choice| InferenceChunk {
+        text_delta: choice.text,
+        finished: choice.finish_reason.is_some(),
+        finish_reason: choice.finish_reason,
+        usage,
+        logprobs: choice.logprobs.map(|l| l.into_tokens(choice.token_ids.as_deref())),
+    }))
+}
+
+/// Alternatives from a `{token: logprob}` map, most likely first
+fn sorted_top(alternatives: HashMap<String, f32>) -> Vec<TopLogprob> {
+    let mut top: Vec<TopLogprob> = alternatives.into_iter()
+        .map(|(token, logprob)| TopLogprob { token, token_id: None, logprob })
+        .collect();
+    top.sort_by(|a, b| b.logprob.total_cmp(&a.logprob));
+    top
+}
+
+/// Prompt logprobs keyed by token ID, resolved against the prompt's IDs
+///
+/// The first entry is null because the first token has no context.
+fn prompt_tokens(entries: Vec<Option<HashMap<String, VllmPromptLogprob>>>, ids: &[u32]) -> Vec<TokenLogprob> {
+    entries.into_iter().zip(ids).filter_map(|(entry, id)| {
+        let entry = entry?;
+        let actual = entry.get(&id.to_string())?;
+
+        let mut top: Vec<TopLogprob> = entry.iter().map(|(key, alt)| TopLogprob {
+            token: alt.decoded_token.clone().unwrap_or_default(),
+            token_id: key.parse().ok(),
+            logprob: alt.logprob,
+        }).collect();
+        top.sort_by(|a, b| b.logprob.total_cmp(&a.logprob));
+
+        Some(TokenLogprob {
+            token: actual.decoded_token.clone().unwrap_or_default(),
+            token_id: Some(*id),
+            logprob: actual.logprob,
+            top_logprobs: top,
+        })
+    }).collect()
+}
+
+/// Sampling fields shared by vLLM's completion and chat requests
 #[derive(Debug, Serialize)]
-struct VllmCompletionRequest {
-    model: String,
-    prompt: String,
+struct VllmSamplingParams {
     max_tokens: u32,
     temperature: f32,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -103,35 +294,305 @@ struct VllmCompletionRequest {
     frequency_penalty: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     stop: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    seed: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    repetition_penalty: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    ignore_eos: bool,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    logit_bias: BTreeMap<u32, f32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    stop_token_ids: Vec<u32>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    bad_words: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    skip_special_tokens: Option<bool>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    include_stop_str_in_output: bool,
+    #[serde(skip_serializing_if = "is_one")]
+    n: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    best_of: Option<u32>,
+}
+
+/// `n` is omitted when it is vLLM's default of 1
+fn is_one(n: &u32) -> bool {
+    *n == 1
+}
+
+impl From<&SamplingParams> for VllmSamplingParams {
+    fn from(sampling: &SamplingParams) -> Self {
+        Self {
+            max_tokens: sampling.max_tokens,
+            temperature: sampling.temperature,
+            top_p: sampling.top_p,
+            top_k: sampling.top_k,
+            presence_penalty: sampling.presence_penalty,
+            frequency_penalty: sampling.frequency_penalty,
+            stop: if sampling.stop_sequences.is_empty() {
+                None
+            } else {
+                Some(sampling.stop_sequences.clone())
+            },
+            seed: sampling.seed,
+            min_p: sampling.min_p,
+            repetition_penalty: sampling.repetition_penalty,
+            min_tokens: sampling.min_tokens,
+            ignore_eos: sampling.ignore_eos,
+            logit_bias: sampling.logit_bias.clone(),
+            stop_token_ids: sampling.stop_token_ids.clone(),
+            bad_words: sampling.bad_words.clone(),
+            skip_special_tokens: sampling.skip_special_tokens,
+            include_stop_str_in_output: sampling.include_stop_str_in_output,
+            n: sampling.n,
+            best_of: sampling.best_of,
+        }
+    }
+}
+
+/// vLLM completion request format (OpenAI-compatible)
+#[derive(Debug, Serialize)]
+struct VllmCompletionRequest {
+    model: String,
+    prompt: VllmPrompt,
+    #[serde(flatten)]
+    sampling: VllmSamplingParams,
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<VllmStreamOptions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    logprobs: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prompt_logprobs: Option<u32>,
+    /// vLLM extension: report token IDs alongside logprobs; older servers
+    /// ignore it, leaving output token IDs unset and prompt logprobs
+    /// unresolvable
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    return_token_ids: bool,
+}
+
+/// A single prompt, or a batch completed in one request
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum VllmPrompt {
+    Single(String),
+    Batch(Vec<String>),
+}
+
+/// Usage reporting for streamed responses
+#[derive(Debug, Serialize)]
+struct VllmStreamOptions {
+    include_usage: bool,
+    /// vLLM extension: attach running usage to every chunk, not just a
+    /// trailing usage-only event
+    continuous_usage_stats: bool,
+}
+
+impl VllmCompletionRequest {
+    /// Build the wire request for an Axon inference request
+    fn new(model: &str, request: &InferenceRequest, stream: bool) -> Self {
+        Self::build(model, VllmPrompt::Single(request.prompt.clone()), &request.sampling, stream)
+    }
+
+    /// Build the wire request for a batch of prompts
+    fn batch(model: &str, request: &BatchInferenceRequest) -> Self {
+        Self::build(model, VllmPrompt::Batch(request.prompts.clone()), &request.sampling, false)
+    }
+
+    fn build(model: &str, prompt: VllmPrompt, sampling: &SamplingParams, stream: bool) -> Self {
+        Self {
+            model: model.to_string(),
+            prompt,
+            sampling: VllmSamplingParams::from(sampling),
+            stream,
+            stream_options: stream.then_some(VllmStreamOptions {
+                include_usage: true,
+                continuous_usage_stats: true,
+            }),
+            logprobs: sampling.logprobs,
+            prompt_logprobs: sampling.prompt_logprobs,
+            return_token_ids: sampling.logprobs.is_some() || sampling.prompt_logprobs.is_some(),
+        }
+    }
+}
+
+/// vLLM chat completion request format (OpenAI-compatible)
+#[derive(Debug, Serialize)]
+struct VllmChatRequest {
+    model: String,
+    messages: Vec<OpenAiMessage>,
+    #[serde(flatten)]
+    sampling: VllmSamplingParams,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    logprobs: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    top_logprobs: Option<u32>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    return_token_ids: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tools: Vec<OpenAiTool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_choice: Option<OpenAiToolChoice>,
+}
+
+impl VllmChatRequest {
+    /// Build the wire request for an Axon chat request
+    fn new(model: &str, request: &ChatRequest) -> Self {
+        Self {
+            model: model.to_string(),
+            messages: request.messages.iter().map(OpenAiMessage::from).collect(),
+            tools: request.tools.iter().map(OpenAiTool::from).collect(),
+            tool_choice: request.tool_choice.as_ref().map(OpenAiToolChoice::from),
+            sampling: VllmSamplingParams::from(&request.sampling),
+            logprobs: request.sampling.logprobs.is_some(),
+            top_logprobs: request.sampling.logprobs,
+            return_token_ids: request.sampling.logprobs.is_some(),
+        }
+    }
 }
 
 /// vLLM completion response format (OpenAI-compatible)
 #[derive(Debug, Deserialize)]
 struct VllmCompletionResponse {
-    id: String,
     choices: Vec<VllmChoice>,
-    usage: VllmUsage,
+    usage: OpenAiUsage,
 }
 
 #[derive(Debug, Deserialize)]
 struct VllmChoice {
+    #[serde(default)]
+    index: usize,
     text: String,
-    #[serde(rename = "finish_reason")]
     finish_reason: String,
-    #[serde(rename = "text_tokens")]
-    text_tokens: Option<usize>,
+    logprobs: Option<VllmLogprobs>,
+    token_ids: Option<Vec<u32>>,
+    prompt_logprobs: Option<Vec<Option<HashMap<String, VllmPromptLogprob>>>>,
+    prompt_token_ids: Option<Vec<u32>>,
 }
 
+impl VllmChoice {
+    /// Convert to Axon's format; vLLM numbers the choices of a batch
+    /// `prompt_index * n + index`
+    ///
+    /// Fails if `sampling` asked for prompt logprobs but the server did not
+    /// return them with the prompt's token IDs.
+    fn into_choice(self, sampling: &SamplingParams) -> Result<CompletionChoice> {
+        let n = sampling.n.max(1) as usize;
+        let logprobs = self.logprobs.map(|l| l.into_tokens(self.token_ids.as_deref()));
+        let prompt_logprobs = match (self.prompt_logprobs, self.prompt_token_ids) {
+            (Some(entries), Some(ids)) => Some(prompt_tokens(entries, &ids)),
+            // Without the IDs, the prompt's own token can't be told apart
+            // from the alternatives in each entry
+            _ if sampling.prompt_logprobs.is_some() => {
+                return Err(AxonError::InferenceFailed(
+                    "vLLM returned no prompt_token_ids; prompt_logprobs needs a server that supports return_token_ids".into(),
+                ));
+            }
+            _ => None,
+        };
+
+        Ok(CompletionChoice {
+            prompt_index: self.index / n,
+            index: self.index % n,
+            text: self.text,
+            finish_reason: self.finish_reason,
+            logprobs,
+            prompt_logprobs,
+        })
+    }
+}
+
+/// Log probabilities of a completion choice, one entry per token
 #[derive(Debug, Deserialize)]
-struct VllmUsage {
-    prompt_tokens: usize,
-    completion_tokens: usize,
-    total_tokens: usize,
+struct VllmLogprobs {
+    tokens: Vec<String>,
+    token_logprobs: Vec<Option<f32>>,
+    #[serde(default)]
+    top_logprobs: Vec<Option<HashMap<String, f32>>>,
+}
+
+impl VllmLogprobs {
+    /// Convert to Axon's format, pairing tokens with `token_ids` if known
+    fn into_tokens(self, token_ids: Option<&[u32]>) -> Vec<TokenLogprob> {
+        let mut top = self.top_logprobs.into_iter();
+        self.tokens.into_iter().zip(self.token_logprobs).enumerate().map(|(i, (token, logprob))| TokenLogprob {
+            token,
+            token_id: token_ids.and_then(|ids| ids.get(i).copied()),
+            logprob: logprob.unwrap_or(f32::NEG_INFINITY),
+            top_logprobs: top.next().flatten().map(sorted_top).unwrap_or_default(),
+        }).collect()
+    }
+}
+
+/// One candidate in a prompt position's logprobs
+#[derive(Debug, Deserialize)]
+struct VllmPromptLogprob {
+    logprob: f32,
+    decoded_token: Option<String>,
+}
+
+/// A model or LoRA adapter served by vLLM, as listed by `/v1/models`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct ServedModel {
+    /// Name to send as `model` in requests
+    pub id: String,
+
+    /// Path or Hub ID the weights were loaded from
+    #[serde(default)]
+    pub root: Option<String>,
+
+    /// Base model, if this is a LoRA adapter
+    #[serde(default)]
+    pub parent: Option<String>,
+
+    /// Maximum context length (prompt plus generated tokens)
+    #[serde(default)]
+    pub max_model_len: Option<usize>,
+
+    /// Owner reported by the server (usually `vllm`)
+    #[serde(default)]
+    pub owned_by: Option<String>,
+}
+
+impl ServedModel {
+    /// Whether this is a LoRA adapter rather than a base model
+    pub fn is_adapter(&self) -> bool {
+        self.parent.is_some()
+    }
+}
+
+/// Response of `/v1/models`
+#[derive(Debug, Deserialize)]
+struct VllmModelList {
+    data: Vec<ServedModel>,
+}
+
+/// A single server-sent event from a streaming completion
+#[derive(Debug, Deserialize)]
+struct VllmStreamResponse {
+    #[serde(default)]
+    choices: Vec<VllmStreamChoice>,
+    usage: Option<OpenAiUsage>,
+    error: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct VllmStreamChoice {
+    text: String,
+    finish_reason: Option<String>,
+    logprobs: Option<VllmLogprobs>,
+    token_ids: Option<Vec<u32>>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::ChatMessage;
 
     #[test]
     fn test_vllm_client_new() {
@@ -143,18 +604,231 @@ mod tests {
     fn test_completion_request_serialization() {
         let req = VllmCompletionRequest {
             model: "test".to_string(),
-            prompt: "Hello".to_string(),
-            max_tokens: 100,
-            temperature: 0.7,
-            top_p: Some(0.9),
-            top_k: None,
-            presence_penalty: Some(0.0),
-            frequency_penalty: Some(0.0),
-            stop: None,
+            prompt: VllmPrompt::Single("Hello".to_string()),
+            sampling: VllmSamplingParams::from(&SamplingParams {
+                temperature: 0.7,
+                top_p: Some(0.9),
+                ..Default::default()
+            }),
+            stream: false,
+            stream_options: None,
+            logprobs: None,
+            prompt_logprobs: None,
+            return_token_ids: false,
         };
 
         let json = serde_json::to_string(&req).unwrap();
         assert!(json.contains("\"prompt\":\"Hello\""));
         assert!(json.contains("\"temperature\":0.7"));
+        assert!(!json.contains("ignore_eos"));
+    }
+
+    #[test]
+    fn test_extended_sampling_serialization() {
+        let sampling = SamplingParams {
+            seed: Some(42),
+            min_p: Some(0.05),
+            repetition_penalty: Some(1.1),
+            min_tokens: Some(4),
+            ignore_eos: true,
+            logit_bias: [(50256, -100.0)].into_iter().collect(),
+            stop_token_ids: vec![128009],
+            bad_words: vec!["darn".to_string()],
+            skip_special_tokens: Some(false),
+            include_stop_str_in_output: true,
+            ..Default::default()
+        };
+
+        let json = serde_json::to_value(VllmSamplingParams::from(&sampling)).unwrap();
+        assert_eq!(json["seed"], 42);
+        assert_eq!(json["min_tokens"], 4);
+        assert_eq!(json["ignore_eos"], true);
+        assert_eq!(json["logit_bias"]["50256"], -100.0);
+        assert_eq!(json["stop_token_ids"][0], 128009);
+        assert_eq!(json["bad_words"][0], "darn");
+        assert_eq!(json["skip_special_tokens"], false);
+        assert_eq!(json["include_stop_str_in_output"], true);
+    }
+
+    #[test]
+    fn test_stream_request_serialization() {
+        let request = InferenceRequest {
+            prompt: "Hello".to_string(),
+            ..Default::default()
+        };
+
+        let json = serde_json::to_value(VllmCompletionRequest::new("m", &request, true)).unwrap();
+        assert_eq!(json["stream"], true);
+        assert_eq!(json["stream_options"]["include_usage"], true);
+
+        let json = serde_json::to_value(VllmCompletionRequest::new("m", &request, false)).unwrap();
+        assert!(json.get("stream_options").is_none());
+    }
+
+    #[test]
+    fn test_chat_request_serialization() {
+        let request = ChatRequest {
+            messages: vec![
+                ChatMessage::system("You are terse."),
+                ChatMessage::user("Hi"),
+            ],
+            ..Default::default()
+        };
+
+        let json: serde_json::Value =
+            serde_json::to_value(VllmChatRequest::new("m", &request)).unwrap();
+        assert_eq!(json["model"], "m");
+        assert_eq!(json["messages"][0]["role"], "system");
+        assert_eq!(json["messages"][1]["content"], "Hi");
+        assert_eq!(json["max_tokens"], 100);
+        assert!(json.get("tools").is_none());
+
+        let request = ChatRequest {
+            tools: vec![crate::types::Tool {
+                name: "get_weather".to_string(),
+                description: Some("Current weather in a city".to_string()),
+                parameters: serde_json::json!({"type": "object"}),
+            }],
+            tool_choice: Some(crate::types::ToolChoice::Auto),
+            ..request
+        };
+        let json = serde_json::to_value(VllmChatRequest::new("m", &request)).unwrap();
+        assert_eq!(json["tools"][0]["function"]["name"], "get_weather");
+        assert_eq!(json["tool_choice"], "auto");
+    }
+
+    #[test]
+    fn test_model_list_parsing() {
+        let list: VllmModelList = serde_json::from_str(r#"{"object":"list","data":[
+            {"id":"llama","object":"model","owned_by":"vllm","root":"meta-llama/Llama-3.1-8B","parent":null,"max_model_len":8192},
+            {"id":"sql-lora","object":"model","owned_by":"vllm","root":"/adapters/sql","parent":"llama"}
+        ]}"#).unwrap();
+
+        assert_eq!(list.data[0].id, "llama");
+        assert_eq!(list.data[0].max_model_len, Some(8192));
+        assert!(!list.data[0].is_adapter());
+        assert!(list.data[1].is_adapter());
+    }
+
+    #[test]
+    fn test_parse_stream_event() {
+        let chunk = parse_stream_event(
+            r#"{"id":"cmpl-1","choices":[{"index":0,"text":" world","finish_reason":null}]}"#.to_string(),
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(chunk.text_delta, " world");
+        assert!(!chunk.finished);
+
+        let last = parse_stream_event(
+            r#"{"id":"cmpl-1","choices":[{"index":0,"text":"","finish_reason":"length"}],"usage":{"prompt_tokens":3,"completion_tokens":16,"total_tokens":19}}"#.to_string(),
+        )
+        .unwrap()
+        .unwrap();
+        assert!(last.finished);
+        assert_eq!(last.finish_reason.as_deref(), Some("length"));
+        assert_eq!(last.usage.map(|u| u.completion_tokens), Some(16));
+
+        let usage_only = parse_stream_event(r#"{"id":"cmpl-1","choices":[]}"#.to_string()).unwrap();
+        assert!(usage_only.is_none());
+    }
+
+    #[test]
+    fn test_stream_error_event() {
+        let err = parse_stream_event(
+            r#"{"error":{"object":"error","message":"Engine loop has died","type":"InternalServerError","param":null,"code":500}}"#.to_string(),
+        )
+        .unwrap_err();
+
+        match err {
+            AxonError::Api(api) => {
+                assert_eq!(api.status, 500);
+                assert_eq!(api.message, "Engine loop has died");
+                assert_eq!(api.error_type.as_deref(), Some("InternalServerError"));
+            }
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_completion_logprobs_parsing() {
+        let resp: VllmCompletionResponse = serde_json::from_str(r#"{"choices":[{"index":0,"text":" Paris","finish_reason":"length",
+            "logprobs":{"text_offset":[0],"token_logprobs":[-0.05],"tokens":[" Paris"],"top_logprobs":[{" Paris":-0.05," Lyon":-3.2}]},
+            "token_ids":[12366],"prompt_token_ids":[791,6864],
+            "prompt_logprobs":[null,{"6864":{"logprob":-4.1,"rank":3,"decoded_token":" capital"},"1176":{"logprob":-1.2,"rank":1,"decoded_token":" first"}}]}],
+            "usage":{"prompt_tokens":2,"completion_tokens":1,"total_tokens":3}}"#).unwrap();
+        let choice = resp.choices.into_iter().next().unwrap();
+
+        let tokens = choice.logprobs.unwrap().into_tokens(choice.token_ids.as_deref());
+        assert_eq!(tokens[0].token, " Paris");
+        assert_eq!(tokens[0].token_id, Some(12366));
+        assert_eq!(tokens[0].top_logprobs[1].token, " Lyon");
+
+        let prompt = prompt_tokens(choice.prompt_logprobs.unwrap(), &choice.prompt_token_ids.unwrap());
+        assert_eq!(prompt.len(), 1);
+        assert_eq!((prompt[0].token.as_str(), prompt[0].token_id), (" capital", Some(6864)));
+        assert_eq!(prompt[0].top_logprobs[0].token_id, Some(1176));
+
+        let request = InferenceRequest {
+            sampling: SamplingParams { logprobs: Some(2), ..Default::default() },
+            ..Default::default()
+        };
+        let json = serde_json::to_value(VllmCompletionRequest::new("m", &request, false)).unwrap();
+        assert_eq!(json["logprobs"], 2);
+        assert_eq!(json["return_token_ids"], true);
+    }
+
+    #[test]
+    fn test_prompt_logprobs_without_ids_fail() {
+        // A server that ignores `return_token_ids` sends no prompt_token_ids
+        let choice: VllmChoice = serde_json::from_str(r#"{"index":0,"text":" Paris","finish_reason":"length",
+            "prompt_logprobs":[null,{"6864":{"logprob":-4.1,"rank":3,"decoded_token":" capital"}}]}"#).unwrap();
+        let sampling = SamplingParams { prompt_logprobs: Some(1), ..Default::default() };
+
+        assert!(matches!(choice.into_choice(&sampling), Err(AxonError::InferenceFailed(_))));
+    }
+
+    #[tokio::test]
+    async fn test_prompt_logprobs_rejected_when_streaming() {
+        // Rejected before anything is sent, so no server is needed
+        let client = VllmClient::new("http://127.0.0.1:1".to_string());
+        let request = InferenceRequest {
+            sampling: SamplingParams { prompt_logprobs: Some(1), ..Default::default() },
+            ..Default::default()
+        };
+
+        let result = client.infer_stream("m", request).await;
+        assert!(matches!(result, Err(AxonError::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn test_batch_choices_indexed_by_prompt() {
+        let request = BatchInferenceRequest {
+            prompts: vec!["a".to_string(), "b".to_string()],
+            sampling: SamplingParams { n: 2, best_of: Some(4), ..Default::default() },
+            ..Default::default()
+        };
+        let json = serde_json::to_value(VllmCompletionRequest::batch("m", &request)).unwrap();
+        assert_eq!(json["prompt"][1], "b");
+        assert_eq!((json["n"].as_u64(), json["best_of"].as_u64()), (Some(2), Some(4)));
+
+        let resp: VllmCompletionResponse = serde_json::from_str(r#"{"choices":[
+            {"index":3,"text":"b1","finish_reason":"stop"},
+            {"index":0,"text":"a0","finish_reason":"stop"},
+            {"index":2,"text":"b0","finish_reason":"length"}],
+            "usage":{"prompt_tokens":2,"completion_tokens":6,"total_tokens":8}}"#).unwrap();
+        let choices: Vec<_> = resp.choices.into_iter().map(|c| c.into_choice(&request.sampling).unwrap()).collect();
+        assert_eq!((choices[0].prompt_index, choices[0].index), (1, 1));
+        assert_eq!((choices[2].prompt_index, choices[2].index), (1, 0));
+
+        let single = InferenceRequest {
+            sampling: SamplingParams { n: 3, ..Default::default() },
+            ..Default::default()
+        };
+        assert!(matches!(check_single(&single.sampling), Err(AxonError::InvalidConfig(_))));
+        assert!(matches!(check_n(&SamplingParams { n: 0, ..Default::default() }), Err(AxonError::InvalidConfig(_))));
+        let err = check_n(&SamplingParams { n: 3, best_of: Some(2), ..Default::default() }).unwrap_err();
+        assert_eq!(err.to_string(), "Invalid configuration: best_of (2) must be at least n (3)");
+        assert!(serde_json::to_value(VllmSamplingParams::from(&SamplingParams::default())).unwrap().get("n").is_none());
     }
 }
@@ -1,6 +1,17 @@
 //! vLLM-specific configuration
 
+use crate::error::{AxonError, Result};
+use crate::logs::{LogSinks, TracingSink};
 use crate::types::ModelConfig;
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::launcher::VllmLauncher;
+use super::pidfile::OrphanPolicy;
+use super::supervisor::RestartPolicy;
 
 /// vLLM-specific configuration derived from ModelConfig
 #[derive(Debug, Clone)]
@@ -11,8 +22,8 @@ pub struct VllmConfig {
     /// Host to bind to
     pub host: String,
 
-    /// Port to bind to
-    pub port: u16,
+    /// Port to bind to; `None` picks a free ephemeral port at spawn time
+    pub port: Option<u16>,
 
     /// Tensor parallel size (multi-GPU)
     pub tensor_parallel_size: Option<usize>,
@@ -22,20 +33,307 @@ pub struct VllmConfig {
 
     /// Data type (auto, half, bfloat16, float32)
     pub dtype: Option<String>,
+
+    /// Fraction of GPU memory vLLM may use (0-1)
+    pub gpu_memory_utilization: Option<f32>,
+
+    /// Quantization method (e.g., awq, gptq, fp8)
+    pub quantization: Option<String>,
+
+    /// Enable or disable automatic prefix caching; engine default if unset
+    pub enable_prefix_caching: Option<bool>,
+
+    /// Maximum concurrent sequences per iteration
+    pub max_num_seqs: Option<usize>,
+
+    /// Maximum number of tokens per iteration
+    pub max_num_batched_tokens: Option<usize>,
+
+    /// KV cache data type (e.g., auto, fp8)
+    pub kv_cache_dtype: Option<String>,
+
+    /// Pipeline parallel size (multi-node)
+    pub pipeline_parallel_size: Option<usize>,
+
+    /// Model revision (branch, tag or commit) to download
+    pub revision: Option<String>,
+
+    /// Directory to download and cache weights in
+    pub download_dir: Option<String>,
+
+    /// Model name reported by the API, instead of `model_name`
+    pub served_model_name: Option<String>,
+
+    /// Allow custom model code from the Hugging Face Hub
+    pub trust_remote_code: bool,
+
+    /// Disable CUDA graphs and always run in eager mode
+    pub enforce_eager: bool,
+
+    /// Random seed
+    pub seed: Option<u64>,
+
+    /// Additional engine flags passed through verbatim, as `(flag, value)`
+    ///
+    /// Flags without a leading `--` get one, with `_` replaced by `-`. An
+    /// empty value emits the flag alone.
+    pub extra_args: Vec<(String, String)>,
+
+    /// Command used to start the server
+    pub launcher: VllmLauncher,
+
+    /// Environment variables for the engine (e.g., `HF_TOKEN`,
+    /// `CUDA_VISIBLE_DEVICES`, `VLLM_*`), added to the inherited environment
+    pub env: Vec<(String, String)>,
+
+    /// Working directory for the launched command
+    pub working_dir: Option<PathBuf>,
+
+    /// Where engine stdout/stderr lines are forwarded
+    ///
+    /// Defaults to a `TracingSink`. The pipes are drained even when empty.
+    pub log_sinks: LogSinks,
+
+    /// Number of recent output lines attached to startup errors
+    pub log_tail_lines: usize,
+
+    /// How long startup may go without progress in the engine's logs
+    /// before it is abandoned
+    pub ready_timeout: Duration,
+
+    /// How long startup may take in total, even while it keeps progressing
+    pub ready_deadline: Duration,
+
+    /// Delay between readiness probes
+    pub ready_poll_interval: Duration,
+
+    /// HTTP path probed for readiness (e.g., `/health` or `/v1/models`)
+    pub ready_probe_path: String,
+
+    /// Directory for pidfiles of spawned engines; none are written if unset
+    pub state_dir: Option<PathBuf>,
+
+    /// What to do with an engine a previous run left in `state_dir`
+    pub orphan_policy: OrphanPolicy,
+
+    /// Whether the engine is restarted after it exits
+    pub restart_policy: RestartPolicy,
+
+    /// Delay before the first restart; doubles for each further restart
+    /// within `restart_window`
+    pub restart_backoff: Duration,
+
+    /// Upper bound on the restart delay
+    pub restart_backoff_max: Duration,
+
+    /// Restarts allowed within `restart_window` before giving up
+    pub max_restarts: usize,
+
+    /// Sliding window over which restarts are counted
+    pub restart_window: Duration,
+}
+
+impl Default for VllmConfig {
+    fn default() -> Self {
+        Self::base(ModelConfig::default())
+    }
 }
 
 impl VllmConfig {
     /// Create vLLM config from generic ModelConfig
-    pub fn from_model_config(config: ModelConfig) -> Self {
+    ///
+    /// `max_batch_size` becomes `max_num_seqs`. `extra_options` keys naming
+    /// a typed field (e.g., `gpu_memory_utilization`, in snake or kebab
+    /// case) set that field; any other key is passed to vLLM as a raw flag.
+    pub fn from_model_config(mut config: ModelConfig) -> Result<Self> {
+        let options = std::mem::take(&mut config.extra_options);
+        let mut vllm_config = Self::base(config);
+
+        for (key, value) in &options {
+            vllm_config.set_option(key, value)?;
+        }
+
+        Ok(vllm_config)
+    }
+
+    fn base(config: ModelConfig) -> Self {
         Self {
             model_name: config.model_name,
             host: config.host.unwrap_or_else(|| "127.0.0.1".to_string()),
-            port: config.port.unwrap_or(8000),
+            port: config.port,
             tensor_parallel_size: config.tensor_parallel_size,
             max_sequence_length: config.max_sequence_length,
             dtype: config.dtype,
+            gpu_memory_utilization: None,
+            quantization: None,
+            enable_prefix_caching: None,
+            max_num_seqs: config.max_batch_size,
+            max_num_batched_tokens: None,
+            kv_cache_dtype: None,
+            pipeline_parallel_size: None,
+            revision: None,
+            download_dir: None,
+            served_model_name: None,
+            trust_remote_code: false,
+            enforce_eager: false,
+            seed: None,
+            extra_args: Vec::new(),
+            launcher: VllmLauncher::default(),
+            env: Vec::new(),
+            working_dir: None,
+            log_sinks: default_log_sinks(),
+            log_tail_lines: 50,
+            ready_timeout: Duration::from_secs(120),
+            ready_deadline: Duration::from_secs(30 * 60),
+            ready_poll_interval: Duration::from_secs(2),
+            ready_probe_path: "/health".to_string(),
+            state_dir: None,
+            orphan_policy: OrphanPolicy::default(),
+            restart_policy: RestartPolicy::default(),
+            restart_backoff: Duration::from_secs(1),
+            restart_backoff_max: Duration::from_secs(60),
+            max_restarts: 5,
+            restart_window: Duration::from_secs(600),
+        }
+    }
+
+    /// Apply one `extra_options` entry
+    fn set_option(&mut self, key: &str, value: &str) -> Result<()> {
+        let name = key.trim_start_matches("--").replace('-', "_");
+
+        match name.as_str() {
+            "gpu_memory_utilization" => self.gpu_memory_utilization = Some(parse(key, value)?),
+            "quantization" => self.quantization = Some(value.to_string()),
+            "enable_prefix_caching" => self.enable_prefix_caching = Some(parse_flag(key, value)?),
+            "max_num_seqs" => self.max_num_seqs = Some(parse(key, value)?),
+            "max_num_batched_tokens" => self.max_num_batched_tokens = Some(parse(key, value)?),
+            "kv_cache_dtype" => self.kv_cache_dtype = Some(value.to_string()),
+            "pipeline_parallel_size" => self.pipeline_parallel_size = Some(parse(key, value)?),
+            "revision" => self.revision = Some(value.to_string()),
+            "download_dir" => self.download_dir = Some(value.to_string()),
+            "served_model_name" => self.served_model_name = Some(value.to_string()),
+            "trust_remote_code" => self.trust_remote_code = parse_flag(key, value)?,
+            "enforce_eager" => self.enforce_eager = parse_flag(key, value)?,
+            "seed" => self.seed = Some(parse(key, value)?),
+            _ => self.extra_args.push((key.to_string(), value.to_string())),
         }
+
+        Ok(())
     }
+
+    /// Command-line arguments for the vLLM OpenAI-compatible server
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--model".to_string(),
+            self.model_name.clone(),
+            "--host".to_string(),
+            self.host.clone(),
+        ];
+
+        let mut push = |flag: &str, value: Option<String>| {
+            if let Some(value) = value {
+                args.push(flag.to_string());
+                args.push(value);
+            }
+        };
+
+        push("--port", self.port.map(|v| v.to_string()));
+        push("--tensor-parallel-size", self.tensor_parallel_size.map(|v| v.to_string()));
+        push("--pipeline-parallel-size", self.pipeline_parallel_size.map(|v| v.to_string()));
+        push("--max-model-len", self.max_sequence_length.map(|v| v.to_string()));
+        push("--dtype", self.dtype.clone().filter(|d| d != "auto"));
+        push("--gpu-memory-utilization", self.gpu_memory_utilization.map(|v| v.to_string()));
+        push("--quantization", self.quantization.clone());
+        push("--max-num-seqs", self.max_num_seqs.map(|v| v.to_string()));
+        push("--max-num-batched-tokens", self.max_num_batched_tokens.map(|v| v.to_string()));
+        push("--kv-cache-dtype", self.kv_cache_dtype.clone());
+        push("--revision", self.revision.clone());
+        push("--download-dir", self.download_dir.clone());
+        push("--served-model-name", self.served_model_name.clone());
+        push("--seed", self.seed.map(|v| v.to_string()));
+
+        match self.enable_prefix_caching {
+            Some(true) => args.push("--enable-prefix-caching".to_string()),
+            Some(false) => args.push("--no-enable-prefix-caching".to_string()),
+            None => {}
+        }
+        if self.trust_remote_code {
+            args.push("--trust-remote-code".to_string());
+        }
+        if self.enforce_eager {
+            args.push("--enforce-eager".to_string());
+        }
+
+        for (flag, value) in &self.extra_args {
+            if flag.starts_with('-') {
+                args.push(flag.clone());
+            } else {
+                args.push(format!("--{}", flag.replace('_', "-")));
+            }
+            if !value.is_empty() {
+                args.push(value.clone());
+            }
+        }
+
+        args
+    }
+
+    /// Check that the configured port is free, or pick one if unset
+    ///
+    /// An ephemeral port is found by binding port 0 and releasing it, so
+    /// another process could in principle claim it before vLLM binds.
+    pub(crate) fn reserve_port(&self) -> Result<u16> {
+        let listener = TcpListener::bind((self.bind_host(), self.port.unwrap_or(0)))
+            .map_err(|e| match self.port {
+                Some(port) => AxonError::InvalidConfig(format!(
+                    "cannot bind vLLM to {}:{}: {}", self.host, port, e
+                )),
+                None => AxonError::InvalidConfig(format!(
+                    "cannot find a free port on {}: {}", self.host, e
+                )),
+            })?;
+
+        Ok(listener.local_addr()?.port())
+    }
+
+    /// Host clients should connect to
+    ///
+    /// A wildcard bind address (`0.0.0.0` or `::`) is reached via loopback.
+    pub(crate) fn connect_host(&self) -> &str {
+        connect_host(&self.host)
+    }
+
+    /// Host in the form `TcpListener::bind` accepts
+    fn bind_host(&self) -> &str {
+        self.host.trim_start_matches('[').trim_end_matches(']')
+    }
+}
+
+/// Address to connect to for a server bound to `host`
+pub(crate) fn connect_host(host: &str) -> &str {
+    match host {
+        "0.0.0.0" => "127.0.0.1",
+        "::" | "[::]" => "[::1]",
+        host => host,
+    }
+}
+
+fn parse<T: FromStr>(key: &str, value: &str) -> Result<T> {
+    value.parse().map_err(|_| {
+        AxonError::InvalidConfig(format!("invalid value '{}' for vLLM option '{}'", value, key))
+    })
+}
+
+/// Parse a boolean option; an empty value means `true`
+fn parse_flag(key: &str, value: &str) -> Result<bool> {
+    if value.is_empty() { Ok(true) } else { parse(key, value) }
+}
+
+/// Engine output goes to `tracing` unless configured otherwise
+fn default_log_sinks() -> LogSinks {
+    let mut sinks = LogSinks::new();
+    sinks.push(Arc::new(TracingSink::new("vllm")));
+    sinks
 }
 
 #[cfg(test)]
@@ -54,11 +352,11 @@ mod tests {
             ..Default::default()
         };
 
-        let vllm_config = VllmConfig::from_model_config(model_config);
+        let vllm_config = VllmConfig::from_model_config(model_config).unwrap();
 
         assert_eq!(vllm_config.model_name, "meta-llama/Llama-2-7b");
         assert_eq!(vllm_config.host, "0.0.0.0");
-        assert_eq!(vllm_config.port, 8080);
+        assert_eq!(vllm_config.port, Some(8080));
         assert_eq!(vllm_config.tensor_parallel_size, Some(2));
     }
 
@@ -69,9 +367,77 @@ mod tests {
             ..Default::default()
         };
 
-        let vllm_config = VllmConfig::from_model_config(model_config);
+        let vllm_config = VllmConfig::from_model_config(model_config).unwrap();
 
         assert_eq!(vllm_config.host, "127.0.0.1");
-        assert_eq!(vllm_config.port, 8000);
+        assert_eq!(vllm_config.port, Some(8000));
+        assert_eq!(vllm_config.max_num_seqs, None);
+        assert!(!vllm_config.to_args().contains(&"--max-num-seqs".to_string()));
+    }
+
+    #[test]
+    fn test_extra_options_to_args() {
+        let vllm_config = VllmConfig::from_model_config(ModelConfig {
+            model_name: "m".to_string(),
+            max_batch_size: Some(64),
+            extra_options: vec![
+                ("gpu_memory_utilization".to_string(), "0.85".to_string()),
+                ("enable-prefix-caching".to_string(), "".to_string()),
+                ("trust_remote_code".to_string(), "true".to_string()),
+                ("swap_space".to_string(), "8".to_string()),
+                ("--disable-log-requests".to_string(), "".to_string()),
+            ],
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(vllm_config.gpu_memory_utilization, Some(0.85));
+        assert_eq!(vllm_config.max_num_seqs, Some(64));
+
+        let args = vllm_config.to_args().join(" ");
+        assert!(args.starts_with("--model m --host 127.0.0.1 --port 8000"));
+        assert!(args.contains("--gpu-memory-utilization 0.85"));
+        assert!(args.contains("--max-num-seqs 64"));
+        assert!(args.contains("--enable-prefix-caching"));
+        assert!(args.contains("--trust-remote-code"));
+        assert!(args.ends_with("--swap-space 8 --disable-log-requests"));
+        assert!(!args.contains("--dtype"));
+    }
+
+    #[test]
+    fn test_invalid_option_rejected() {
+        let result = VllmConfig::from_model_config(ModelConfig {
+            model_name: "m".to_string(),
+            extra_options: vec![("max_num_seqs".to_string(), "lots".to_string())],
+            ..Default::default()
+        });
+
+        assert!(matches!(result, Err(AxonError::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn test_connect_host() {
+        let mut config = VllmConfig {
+            host: "0.0.0.0".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(config.connect_host(), "127.0.0.1");
+
+        config.host = "10.0.0.5".to_string();
+        assert_eq!(config.connect_host(), "10.0.0.5");
+    }
+
+    #[test]
+    fn test_reserve_port() {
+        let mut config = VllmConfig {
+            port: None,
+            ..Default::default()
+        };
+        let port = config.reserve_port().unwrap();
+        assert_ne!(port, 0);
+
+        let _taken = TcpListener::bind(("127.0.0.1", port)).unwrap();
+        config.port = Some(port);
+        assert!(matches!(config.reserve_port(), Err(AxonError::InvalidConfig(_))));
     }
 }
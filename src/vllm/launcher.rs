@@ -0,0 +1,275 @@
+//! How the vLLM server process is launched
+//!
+//! vLLM may be installed system-wide, in a virtualenv or conda env, or only
+//! available as a container image. A `VllmLauncher` turns a `VllmConfig`
+//! into the command that starts the OpenAI-compatible server.
+
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::process::Command;
+
+use super::config::VllmConfig;
+
+/// Command used to start the vLLM server
+#[derive(Debug, Clone, PartialEq)]
+pub enum VllmLauncher {
+    /// `<interpreter> -m vllm.entrypoints.openai.api_server`
+    ///
+    /// Point `interpreter` at a venv or conda env's `bin/python` to use the
+    /// vLLM installed there.
+    Python {
+        /// Python interpreter (e.g., `python3` or `/opt/venv/bin/python`)
+        interpreter: PathBuf,
+    },
+
+    /// The `vllm serve <model>` CLI
+    Serve {
+        /// Path to the `vllm` executable
+        executable: PathBuf,
+    },
+
+    /// Any executable, given `args` followed by the engine arguments
+    Custom {
+        /// Executable to run
+        program: PathBuf,
+        /// Arguments placed before the engine arguments
+        args: Vec<String>,
+    },
+
+    /// `docker run` / `podman run` of a vLLM image
+    Container(ContainerLauncher),
+}
+
+impl Default for VllmLauncher {
+    fn default() -> Self {
+        Self::Python {
+            interpreter: PathBuf::from("python"),
+        }
+    }
+}
+
+/// Container runtime CLI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ContainerRuntime {
+    /// `docker`
+    #[default]
+    Docker,
+    /// `podman`
+    Podman,
+}
+
+impl ContainerRuntime {
+    /// Name of the runtime's CLI executable
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Docker => "docker",
+            Self::Podman => "podman",
+        }
+    }
+}
+
+/// Runs vLLM from a container image
+///
+/// The container shares the host network, so `host` and `port` in
+/// `VllmConfig` apply unchanged, and the host IPC namespace, which vLLM's
+/// tensor-parallel workers need for shared memory. The container is removed
+/// when it exits.
+///
+/// Signals sent to the engine only reach the runtime CLI, which forwards
+/// SIGTERM but cannot forward SIGKILL. The container is therefore named
+/// after its port, and stopped or killed by that name through the runtime.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContainerLauncher {
+    /// Container runtime to invoke
+    pub runtime: ContainerRuntime,
+
+    /// Image whose entrypoint is the vLLM server (e.g., `vllm/vllm-openai:latest`)
+    pub image: String,
+
+    /// GPUs to expose (e.g., `all` or `device=0,1`); none if unset
+    pub gpus: Option<String>,
+
+    /// Bind mounts as `(host path, container path)`, e.g. the HF cache
+    pub volumes: Vec<(PathBuf, PathBuf)>,
+
+    /// Additional runtime flags placed before the image
+    pub extra_args: Vec<String>,
+}
+
+impl ContainerLauncher {
+    /// Run `image` with Docker, exposing all GPUs
+    pub fn new(image: impl Into<String>) -> Self {
+        Self {
+            runtime: ContainerRuntime::Docker,
+            image: image.into(),
+            gpus: Some("all".to_string()),
+            volumes: Vec::new(),
+            extra_args: Vec::new(),
+        }
+    }
+
+    /// Name given to the container serving `config`
+    pub fn container_name(config: &VllmConfig) -> String {
+        format!("axon-vllm-{}", config.port.unwrap_or(8000))
+    }
+
+    /// `<runtime> stop`, which waits `grace` before killing the container
+    pub(crate) fn stop_command(&self, config: &VllmConfig, grace: Duration) -> std::process::Command {
+        let mut cmd = std::process::Command::new(self.runtime.as_str());
+        cmd.args(["stop", "-t", &grace.as_secs().to_string(), &Self::container_name(config)]);
+        cmd
+    }
+
+    /// `<runtime> kill`, which stops the container immediately
+    pub(crate) fn kill_command(&self, config: &VllmConfig) -> std::process::Command {
+        let mut cmd = std::process::Command::new(self.runtime.as_str());
+        cmd.args(["kill", &Self::container_name(config)]);
+        cmd
+    }
+
+    /// `<runtime> rm -f`, which removes a container left behind under the
+    /// same name, e.g. by a crashed run that never reached `--rm`
+    pub(crate) fn remove_command(&self, config: &VllmConfig) -> std::process::Command {
+        let mut cmd = std::process::Command::new(self.runtime.as_str());
+        cmd.args(["rm", "-f", &Self::container_name(config)]);
+        cmd
+    }
+}
+
+impl VllmLauncher {
+    /// Program and arguments that start the server for `config`
+    pub fn command_line(&self, config: &VllmConfig) -> (PathBuf, Vec<String>) {
+        match self {
+            Self::Python { interpreter } => {
+                let mut args = vec!["-m".to_string(), "vllm.entrypoints.openai.api_server".to_string()];
+                args.extend(config.to_args());
+                (interpreter.clone(), args)
+            }
+            Self::Serve { executable } => {
+                // `vllm serve` takes the model as a positional argument
+                let mut args = vec!["serve".to_string()];
+                args.extend(config.to_args().into_iter().skip(1));
+                (executable.clone(), args)
+            }
+            Self::Custom { program, args } => {
+                let mut args = args.clone();
+                args.extend(config.to_args());
+                (program.clone(), args)
+            }
+            Self::Container(container) => {
+                let mut args = vec![
+                    "run".to_string(),
+                    "--rm".to_string(),
+                    format!("--name={}", ContainerLauncher::container_name(config)),
+                    "--network=host".to_string(),
+                    "--ipc=host".to_string(),
+                ];
+
+                if let Some(gpus) = &container.gpus {
+                    match container.runtime {
+                        ContainerRuntime::Docker => args.push(format!("--gpus={}", gpus)),
+                        // Podman exposes GPUs through CDI device names
+                        ContainerRuntime::Podman => {
+                            args.push(format!("--device=nvidia.com/gpu={}", gpus.trim_start_matches("device=")))
+                        }
+                    }
+                }
+                for (host, guest) in &container.volumes {
+                    args.push("-v".to_string());
+                    args.push(format!("{}:{}", host.display(), guest.display()));
+                }
+                // The engine runs inside the container, so env is forwarded
+                // there. Values are set on the runtime CLI's own environment
+                // by `command`, keeping secrets out of `ps` output.
+                for (key, _) in &config.env {
+                    args.push("-e".to_string());
+                    args.push(key.clone());
+                }
+
+                args.extend(container.extra_args.iter().cloned());
+                args.push(container.image.clone());
+                args.extend(config.to_args());
+                (PathBuf::from(container.runtime.as_str()), args)
+            }
+        }
+    }
+
+    /// Build the command, including `config.env` and `config.working_dir`
+    pub(crate) fn command(&self, config: &VllmConfig) -> Command {
+        let (program, args) = self.command_line(config);
+        let mut cmd = Command::new(program);
+        cmd.args(args);
+
+        cmd.envs(config.env.iter().map(|(k, v)| (k, v)));
+        if let Some(dir) = &config.working_dir {
+            cmd.current_dir(dir);
+        }
+
+        cmd
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> VllmConfig {
+        VllmConfig {
+            model_name: "m".to_string(),
+            max_sequence_length: None,
+            max_num_seqs: None,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_serve_takes_positional_model() {
+        let launcher = VllmLauncher::Serve {
+            executable: PathBuf::from("/opt/venv/bin/vllm"),
+        };
+        let (program, args) = launcher.command_line(&config());
+
+        assert_eq!(program, PathBuf::from("/opt/venv/bin/vllm"));
+        assert_eq!(args.join(" "), "serve m --host 127.0.0.1 --port 8000 --tensor-parallel-size 1");
+    }
+
+    #[test]
+    fn test_container_command_line() {
+        let mut config = config();
+        config.env.push(("HF_TOKEN".to_string(), "secret".to_string()));
+
+        let launcher = VllmLauncher::Container(ContainerLauncher {
+            runtime: ContainerRuntime::Podman,
+            volumes: vec![(PathBuf::from("/data/hf"), PathBuf::from("/root/.cache/huggingface"))],
+            ..ContainerLauncher::new("vllm/vllm-openai:latest")
+        });
+        let (program, args) = launcher.command_line(&config);
+        let args = args.join(" ");
+
+        assert_eq!(program, PathBuf::from("podman"));
+        assert!(args.starts_with("run --rm --name=axon-vllm-8000 --network=host --ipc=host --device=nvidia.com/gpu=all"));
+        assert!(args.contains("-v /data/hf:/root/.cache/huggingface -e HF_TOKEN vllm/vllm-openai:latest --model m"));
+        assert!(!args.contains("secret"));
+
+        // The value reaches the container through the runtime CLI's environment
+        let cmd = launcher.command(&config);
+        let env: Vec<_> = cmd.as_std().get_envs().collect();
+        assert!(env.contains(&(std::ffi::OsStr::new("HF_TOKEN"), Some(std::ffi::OsStr::new("secret")))));
+    }
+
+    #[test]
+    fn test_container_stopped_by_name() {
+        let container = ContainerLauncher::new("vllm/vllm-openai:latest");
+        let config = VllmConfig {
+            port: Some(8123),
+            ..config()
+        };
+
+        let args = |cmd: std::process::Command| -> Vec<String> {
+            cmd.get_args().map(|a| a.to_string_lossy().into_owned()).collect()
+        };
+        assert_eq!(args(container.stop_command(&config, Duration::from_secs(5))), ["stop", "-t", "5", "axon-vllm-8123"]);
+        assert_eq!(args(container.kill_command(&config)), ["kill", "axon-vllm-8123"]);
+        assert_eq!(args(container.remove_command(&config)), ["rm", "-f", "axon-vllm-8123"]);
+    }
+}
@@ -0,0 +1,112 @@
+//! vLLM engine metrics scraped from `/metrics`
+//!
+//! Metric names changed between vLLM's V0 and V1 engines; both spellings
+//! are accepted.
+
+use crate::backend::BackendMetrics;
+use crate::prometheus::{self, Sample};
+
+/// Engine-side load indicators reported by vLLM
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct VllmEngineMetrics {
+    /// Requests currently being decoded
+    pub running_requests: Option<u64>,
+
+    /// Requests queued waiting for KV-cache space
+    pub waiting_requests: Option<u64>,
+
+    /// Fraction of KV-cache blocks in use (0-1)
+    pub kv_cache_usage: Option<f32>,
+
+    /// Fraction of prompt tokens served from the prefix cache (0-1)
+    pub prefix_cache_hit_rate: Option<f32>,
+}
+
+impl VllmEngineMetrics {
+    /// Extract vLLM metrics from a Prometheus scrape body
+    pub fn from_prometheus(text: &str) -> Self {
+        let samples = prometheus::parse(text);
+
+        let kv_cache_usage = prometheus::max(&samples, "vllm:kv_cache_usage_perc")
+            .or_else(|| prometheus::max(&samples, "vllm:gpu_cache_usage_perc"));
+
+        Self {
+            running_requests: prometheus::sum(&samples, "vllm:num_requests_running").map(|v| v as u64),
+            waiting_requests: prometheus::sum(&samples, "vllm:num_requests_waiting").map(|v| v as u64),
+            kv_cache_usage: kv_cache_usage.map(|v| v as f32),
+            prefix_cache_hit_rate: prefix_cache_hit_rate(&samples).map(|v| v as f32),
+        }
+    }
+
+    /// Copy these readings into a metrics snapshot
+    ///
+    /// vLLM's memory is dominated by its preallocated KV cache, so KV-cache
+    /// usage is also reported as `memory_usage_percent`. vLLM does not export
+    /// GPU compute utilization, so `gpu_utilization_percent` is left alone.
+    pub fn apply_to(&self, metrics: &mut BackendMetrics) {
+        let kv_percent = self.kv_cache_usage.map(|v| v * 100.0);
+
+        metrics.running_requests = self.running_requests;
+        metrics.queue_depth = self.waiting_requests;
+        metrics.kv_cache_usage_percent = kv_percent;
+        metrics.memory_usage_percent = kv_percent;
+        metrics.prefix_cache_hit_rate = self.prefix_cache_hit_rate;
+    }
+}
+
+/// Hit rate from the V0 gauge, or from the V1 cumulative counters
+fn prefix_cache_hit_rate(samples: &[Sample]) -> Option<f64> {
+    if let Some(rate) = prometheus::max(samples, "vllm:gpu_prefix_cache_hit_rate") {
+        return Some(rate);
+    }
+
+    for prefix in ["vllm:prefix_cache", "vllm:gpu_prefix_cache"] {
+        let hits = prometheus::sum(samples, &format!("{}_hits_total", prefix));
+        let queries = prometheus::sum(samples, &format!("{}_queries_total", prefix));
+
+        if let (Some(hits), Some(queries)) = (hits, queries) {
+            return Some(if queries > 0.0 { hits / queries } else { 0.0 });
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_v0_metrics() {
+        let metrics = VllmEngineMetrics::from_prometheus(
+            r#"vllm:num_requests_running{model_name="m"} 4.0
+vllm:num_requests_waiting{model_name="m"} 7.0
+vllm:gpu_cache_usage_perc{model_name="m"} 0.85
+vllm:gpu_prefix_cache_hit_rate{model_name="m"} 0.5
+"#,
+        );
+
+        assert_eq!(metrics.running_requests, Some(4));
+        assert_eq!(metrics.waiting_requests, Some(7));
+        assert_eq!(metrics.kv_cache_usage, Some(0.85));
+        assert_eq!(metrics.prefix_cache_hit_rate, Some(0.5));
+    }
+
+    #[test]
+    fn test_v1_metrics_and_apply() {
+        let metrics = VllmEngineMetrics::from_prometheus(
+            r#"vllm:kv_cache_usage_perc{engine="0",model_name="m"} 0.25
+vllm:prefix_cache_queries_total{engine="0",model_name="m"} 400.0
+vllm:prefix_cache_hits_total{engine="0",model_name="m"} 100.0
+"#,
+        );
+        assert_eq!(metrics.prefix_cache_hit_rate, Some(0.25));
+        assert_eq!(metrics.running_requests, None);
+
+        let mut snapshot = BackendMetrics::new();
+        metrics.apply_to(&mut snapshot);
+        assert_eq!(snapshot.kv_cache_usage_percent, Some(25.0));
+        assert_eq!(snapshot.memory_usage_percent, Some(25.0));
+        assert_eq!(snapshot.gpu_utilization_percent, None);
+    }
+}
@@ -0,0 +1,483 @@
+//! Pidfiles for spawned vLLM servers
+//!
+//! If the host process is killed without a chance to clean up, the engine it
+//! spawned keeps running and holds its GPUs and port. With a state directory
+//! configured, each spawned engine is recorded in a pidfile so the next run
+//! can find it and adopt or terminate it according to `OrphanPolicy`.
+//!
+//! A record also names the process that owns the engine. An engine is only
+//! an orphan once that owner has exited, so backends sharing a state
+//! directory never take over each other's live engines.
+
+use crate::error::{AxonError, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+
+use super::config::{connect_host, VllmConfig};
+
+/// What to do with an engine left running by a previous run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OrphanPolicy {
+    /// Terminate it, then spawn a fresh engine
+    #[default]
+    Terminate,
+    /// Connect to it instead of spawning, if it answers health checks and
+    /// was started with the command the new config would run
+    ///
+    /// An adopted engine is not our child, so it is not supervised: the
+    /// restart policy and lifecycle events do not apply, and
+    /// `health_check` reports `Failed` once it exits. It is killed when the
+    /// backend is dropped, but the kernel cannot tie it to this process, so
+    /// if this process dies without unwinding it is left for the next run
+    /// to find again.
+    Adopt,
+    /// Leave it alone; spawning may then fail if it holds the port
+    Ignore,
+}
+
+/// A spawned engine, as recorded in its pidfile
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EngineRecord {
+    /// Process ID, which also leads the engine's process group
+    pub pid: u32,
+
+    /// Host the engine was bound to
+    pub host: String,
+
+    /// Port the engine listens on
+    pub port: u16,
+
+    /// Full command line, program first, with environment values redacted
+    pub command: Vec<String>,
+
+    /// Process that spawned or adopted the engine
+    #[serde(default)]
+    pub owner_pid: u32,
+
+    /// Start time of the owner, guarding against reuse of its PID
+    #[serde(default)]
+    pub owner_start: Option<u64>,
+
+    /// Where this record is stored
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+impl EngineRecord {
+    /// Pidfile location for `config` within `state_dir`
+    ///
+    /// Files are keyed by model name and port, so each engine gets its own
+    /// record. `config.port` must be resolved.
+    pub fn path(state_dir: &Path, config: &VllmConfig) -> PathBuf {
+        state_dir.join(format!("{}{}.json", Self::prefix(config), config.port.unwrap_or(8000)))
+    }
+
+    /// File name prefix shared by the records of `config`'s model
+    fn prefix(config: &VllmConfig) -> String {
+        let name: String = config.model_name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '.' { c } else { '_' })
+            .collect();
+        format!("vllm-{}-", name)
+    }
+
+    /// Make the current process the record's owner
+    pub fn claim(&mut self) {
+        self.owner_pid = std::process::id();
+        self.owner_start = process_start_time(self.owner_pid);
+    }
+
+    /// Write the record to `path`, creating its directory if needed
+    ///
+    /// The file is readable by its owner only.
+    pub fn write(&self) -> Result<()> {
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let json = serde_json::to_vec_pretty(self)
+            .map_err(|e| AxonError::Other(format!("failed to encode pidfile: {}", e)))?;
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(&self.path)?;
+        // `mode` only applies to new files; tighten one left by an older run
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+        file.write_all(&json)?;
+        Ok(())
+    }
+
+    /// Delete the pidfile, unless it has since been taken over by another
+    /// engine (e.g., a restart that reuses the same file)
+    pub fn remove(&self) {
+        if Self::read(&self.path).is_ok_and(|record| record.pid == self.pid) {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+
+    fn read(path: &Path) -> std::io::Result<Self> {
+        let bytes = fs::read(path)?;
+        let mut record: Self = serde_json::from_slice(&bytes)?;
+        record.path = path.to_path_buf();
+        Ok(record)
+    }
+
+    /// Read the engine left behind for `config`, if one is still running
+    /// and its owner has exited
+    ///
+    /// With no port configured, any port's record for the model qualifies.
+    /// Pidfiles whose process is gone, or whose PID now belongs to a
+    /// different program, are removed.
+    pub fn find_orphan(config: &VllmConfig) -> Result<Option<Self>> {
+        let Some(state_dir) = &config.state_dir else {
+            return Ok(None);
+        };
+
+        let paths = match config.port {
+            Some(_) => vec![Self::path(state_dir, config)],
+            None => {
+                let prefix = Self::prefix(config);
+                match fs::read_dir(state_dir) {
+                    Ok(entries) => entries
+                        .filter_map(|entry| entry.ok().map(|e| e.path()))
+                        .filter(|path| {
+                            // `<prefix><port>.json`; other models may share the prefix
+                            let name = path.file_name().unwrap_or_default().to_string_lossy();
+                            name.strip_prefix(&prefix)
+                                .and_then(|rest| rest.strip_suffix(".json"))
+                                .is_some_and(|port| port.parse::<u16>().is_ok())
+                        })
+                        .collect(),
+                    Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+                    Err(e) => return Err(e.into()),
+                }
+            }
+        };
+
+        for path in paths {
+            match Self::read(&path) {
+                // Still owned by a live backend, possibly in this process
+                Ok(record) if record.is_alive() && record.owner_alive() => {}
+                Ok(record) if record.is_alive() => return Ok(Some(record)),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                // Stale or unreadable; nothing left to clean up but the file
+                _ => {
+                    let _ = fs::remove_file(&path);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Whether the recorded process is still running the recorded command
+    pub fn is_alive(&self) -> bool {
+        let exists = unsafe { libc::kill(self.pid as i32, 0) == 0 };
+        exists && self.command_matches()
+    }
+
+    /// Whether the process that owns the engine is still running
+    pub fn owner_alive(&self) -> bool {
+        let exists = self.owner_pid != 0 && unsafe { libc::kill(self.owner_pid as i32, 0) == 0 };
+        exists && process_start_time(self.owner_pid) == self.owner_start
+    }
+
+    /// Guard against PID reuse by comparing the live command line
+    ///
+    /// Only the arguments are compared: launchers such as pyenv shims may
+    /// exec the program under a different path.
+    #[cfg(target_os = "linux")]
+    fn command_matches(&self) -> bool {
+        let Ok(cmdline) = fs::read(format!("/proc/{}/cmdline", self.pid)) else {
+            return false;
+        };
+        let argv: Vec<String> = cmdline
+            .split(|&b| b == 0)
+            .filter(|arg| !arg.is_empty())
+            .map(|arg| String::from_utf8_lossy(arg).into_owned())
+            .collect();
+
+        redact_env(argv).ends_with(self.command.get(1..).unwrap_or_default())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    fn command_matches(&self) -> bool {
+        true
+    }
+
+    /// Whether the engine was started with the command `config` would run
+    ///
+    /// A config without a port matches whichever port the engine chose.
+    pub fn matches(&self, config: &VllmConfig) -> bool {
+        let mut config = config.clone();
+        config.port.get_or_insert(self.port);
+
+        let (program, args) = config.launcher.command_line(&config);
+        redact_env(std::iter::once(program.display().to_string()).chain(args)) == self.command
+    }
+
+    /// Base URL of the engine's HTTP API
+    pub fn base_url(&self) -> String {
+        format!("http://{}:{}", connect_host(&self.host), self.port)
+    }
+
+    /// Terminate the engine's process group, escalating to SIGKILL
+    ///
+    /// The engine is not our child, so it cannot be reaped here; its exit is
+    /// observed by polling instead.
+    pub async fn terminate(&self, grace: Duration) {
+        let deadline = Instant::now() + grace;
+        let group = -(self.pid as i32);
+
+        unsafe {
+            libc::kill(group, libc::SIGTERM);
+        }
+        while unsafe { libc::kill(group, 0) == 0 } && Instant::now() < deadline {
+            sleep(Duration::from_millis(100)).await;
+        }
+        unsafe {
+            libc::kill(group, libc::SIGKILL);
+        }
+    }
+
+    /// Kill the engine's process group immediately, for use in `Drop`
+    ///
+    /// A containerized engine is also killed through its runtime by name,
+    /// since the runtime CLI cannot forward SIGKILL.
+    pub fn kill(&self) {
+        unsafe {
+            libc::kill(-(self.pid as i32), libc::SIGKILL);
+        }
+
+        let name = self.command.iter().find_map(|arg| arg.strip_prefix("--name="));
+        if let (Some(runtime), Some(name)) = (self.command.first(), name) {
+            let _ = std::process::Command::new(runtime)
+                .args(["kill", name])
+                .stdout(std::process::Stdio::null())
+                .stderr(std::process::Stdio::null())
+                .status();
+        }
+    }
+}
+
+/// When process `pid` started, in clock ticks since boot
+#[cfg(target_os = "linux")]
+pub(crate) fn process_start_time(pid: u32) -> Option<u64> {
+    let stat = fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    // The command name may contain spaces; fields resume after its `)`
+    let fields = &stat[stat.rfind(')')? + 1..];
+    fields.split_whitespace().nth(19)?.parse().ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub(crate) fn process_start_time(_pid: u32) -> Option<u64> {
+    None
+}
+
+/// Strip the values from `-e KEY=VALUE` and `--env=KEY=VALUE` arguments,
+/// which may hold secrets such as `HF_TOKEN`
+pub(crate) fn redact_env(argv: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut redacted: Vec<String> = Vec::new();
+    for arg in argv {
+        let after_flag = redacted.last().is_some_and(|prev| prev == "-e" || prev == "--env");
+        let arg = match arg.strip_prefix("--env=") {
+            Some(pair) => format!("--env={}", pair.split('=').next().unwrap_or_default()),
+            None if after_flag => arg.split('=').next().unwrap_or_default().to_string(),
+            None => arg,
+        };
+        redacted.push(arg);
+    }
+    redacted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("axon-state-{}-{}", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_path_is_keyed_by_model_and_port() {
+        let config = VllmConfig {
+            model_name: "meta-llama/Llama-3-8B".to_string(),
+            port: Some(8123),
+            ..Default::default()
+        };
+        let path = EngineRecord::path(Path::new("/var/lib/axon"), &config);
+        assert_eq!(path, Path::new("/var/lib/axon/vllm-meta-llama_Llama-3-8B-8123.json"));
+    }
+
+    #[test]
+    fn test_secrets_are_not_recorded() {
+        let dir = state_dir("secret");
+        let argv = ["docker", "run", "-e", "HF_TOKEN=secret", "--env=API_KEY=secret", "-e", "PLAIN", "img"];
+        let record = EngineRecord {
+            pid: i32::MAX as u32,
+            host: "127.0.0.1".to_string(),
+            port: 8000,
+            command: redact_env(argv.map(String::from)),
+            owner_pid: 0,
+            owner_start: None,
+            path: dir.join("vllm-m-8000.json"),
+        };
+        record.write().unwrap();
+
+        let contents = fs::read_to_string(&record.path).unwrap();
+        assert!(!contents.contains("secret"), "{}", contents);
+        assert_eq!(record.command[2..7], ["-e", "HF_TOKEN", "--env=API_KEY", "-e", "PLAIN"]);
+
+        let mode = fs::metadata(&record.path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_record_matches_config() {
+        let config = VllmConfig {
+            model_name: "m".to_string(),
+            port: None,
+            ..Default::default()
+        };
+        let (program, args) = config.launcher.command_line(&VllmConfig { port: Some(8123), ..config.clone() });
+        let record = EngineRecord {
+            pid: 1,
+            host: "127.0.0.1".to_string(),
+            port: 8123,
+            command: std::iter::once(program.display().to_string()).chain(args).collect(),
+            owner_pid: 0,
+            owner_start: None,
+            path: PathBuf::new(),
+        };
+        assert!(record.matches(&config));
+
+        let changed = VllmConfig {
+            max_sequence_length: Some(1024),
+            ..config
+        };
+        assert!(!record.matches(&changed));
+    }
+
+    #[test]
+    fn test_stale_pidfile_is_removed() {
+        let dir = state_dir("stale");
+        let config = VllmConfig {
+            model_name: "m".to_string(),
+            state_dir: Some(dir.clone()),
+            ..Default::default()
+        };
+        let path = EngineRecord::path(&dir, &config);
+
+        // Above any real pid_max, so never a running process
+        let record = EngineRecord {
+            pid: i32::MAX as u32,
+            host: "127.0.0.1".to_string(),
+            port: 8000,
+            command: vec!["python".to_string()],
+            owner_pid: 0,
+            owner_start: None,
+            path: path.clone(),
+        };
+        record.write().unwrap();
+
+        assert_eq!(EngineRecord::find_orphan(&config).unwrap(), None);
+        assert!(!path.exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_live_orphan_is_found() {
+        let dir = state_dir("live");
+        // Without a port, the record for any port is found
+        let config = VllmConfig {
+            model_name: "m".to_string(),
+            port: None,
+            state_dir: Some(dir.clone()),
+            ..Default::default()
+        };
+        let mut child = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+
+        // The owner is gone, which is what makes the engine an orphan
+        let record = EngineRecord {
+            pid: child.id(),
+            host: "0.0.0.0".to_string(),
+            port: 8123,
+            command: vec!["/bin/sleep".to_string(), "30".to_string()],
+            owner_pid: i32::MAX as u32,
+            owner_start: None,
+            path: EngineRecord::path(&dir, &VllmConfig { port: Some(8123), ..config.clone() }),
+        };
+        record.write().unwrap();
+
+        // /proc/<pid>/cmdline can read empty for a moment right after exec
+        for _ in 0..100 {
+            if record.is_alive() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        let orphan = EngineRecord::find_orphan(&config).unwrap().unwrap();
+        assert_eq!(orphan, record);
+        assert_eq!(orphan.base_url(), "http://127.0.0.1:8123");
+
+        // A record for another PID does not remove the file
+        EngineRecord { pid: 1, ..record.clone() }.remove();
+        assert!(record.path.exists());
+        record.remove();
+        assert!(!record.path.exists());
+
+        child.kill().unwrap();
+        child.wait().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_engine_with_live_owner_is_not_an_orphan() {
+        let dir = state_dir("owned");
+        let config = VllmConfig {
+            model_name: "m".to_string(),
+            port: Some(8124),
+            state_dir: Some(dir.clone()),
+            ..Default::default()
+        };
+        let mut child = std::process::Command::new("sleep").arg("30").spawn().unwrap();
+
+        // Another backend in this process spawned and still supervises it
+        let mut record = EngineRecord {
+            pid: child.id(),
+            host: "127.0.0.1".to_string(),
+            port: 8124,
+            command: vec!["/bin/sleep".to_string(), "30".to_string()],
+            owner_pid: 0,
+            owner_start: None,
+            path: EngineRecord::path(&dir, &config),
+        };
+        record.claim();
+        record.write().unwrap();
+        for _ in 0..100 {
+            if record.is_alive() {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        assert!(record.owner_alive());
+        assert_eq!(EngineRecord::find_orphan(&config).unwrap(), None);
+        assert!(record.path.exists());
+
+        // A new process reusing the owner's PID has a different start time
+        #[cfg(target_os = "linux")]
+        assert!(!EngineRecord { owner_start: record.owner_start.map(|t| t + 1), ..record.clone() }.owner_alive());
+
+        child.kill().unwrap();
+        child.wait().unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,122 +1,250 @@
 //! vLLM process management
 //!
 //! Handles spawning, monitoring, and terminating vLLM server processes.
+//!
+//! The engine runs as an `EngineProcess`: it leads its own process group, so
+//! the Ray or multiprocessing workers vLLM forks for tensor parallelism are
+//! signalled with it, and it is reaped as soon as it exits.
 
+use crate::backend::ProcessExit;
 use crate::error::{AxonError, Result};
-use std::process::{Command, Stdio};
+use crate::logs;
+use crate::process::EngineProcess;
+use std::sync::Arc;
 use std::time::Duration;
-use tokio::time::sleep;
+use tokio::process::Command;
+use tokio::sync::watch;
+use tokio::time::{sleep, timeout_at, Instant};
 
 use super::config::VllmConfig;
+use super::launcher::VllmLauncher;
+use super::pidfile::{redact_env, EngineRecord};
+use super::startup::{StartupPhase, StartupTracker};
+
+/// How long `terminate` waits after SIGTERM before sending SIGKILL
+pub(crate) const TERMINATE_GRACE: Duration = Duration::from_secs(5);
 
 /// A running vLLM server process
 pub struct VllmProcess {
-    /// The child process ID
-    pid: Option<u32>,
+    /// The engine process and its workers
+    engine: EngineProcess,
+
+    /// Port the server listens on
+    port: u16,
+
+    /// Startup phase parsed from engine output
+    startup: Arc<StartupTracker>,
+
+    /// Configuration the process was started with
+    config: VllmConfig,
+
+    /// Pidfile record for this engine, removed when the process is dropped
+    pidfile: Option<EngineRecord>,
 }
 
 impl VllmProcess {
-    /// Spawn a new vLLM server process
-    pub async fn spawn(config: VllmConfig) -> Result<Self> {
-        let mut cmd = Command::new("python");
-        cmd.arg("-m")
-            .arg("vllm.entrypoints.openai.api_server")
-            .arg("--model")
-            .arg(&config.model_name)
-            .arg("--host")
-            .arg(&config.host)
-            .arg("--port")
-            .arg(config.port.to_string())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
-
-        // Add tensor parallelism if specified
-        if let Some(tp) = config.tensor_parallel_size {
-            cmd.arg("--tensor-parallel-size").arg(tp.to_string());
-        }
+    /// Spawn a new vLLM server process with `config.launcher`
+    ///
+    /// Fails with `InvalidConfig` if the configured port is already in use;
+    /// with no port configured, a free one is chosen.
+    pub async fn spawn(mut config: VllmConfig) -> Result<Self> {
+        config.port = Some(config.reserve_port()?);
 
-        // Add max sequence length if specified
-        if let Some(max_len) = config.max_sequence_length {
-            cmd.arg("--max-model-len").arg(max_len.to_string());
+        // Containers are named after the port, which is free, so a container
+        // already holding the name is stale and would make `run` fail
+        if let VllmLauncher::Container(container) = &config.launcher {
+            let mut remove = Command::from(container.remove_command(&config));
+            remove.stdout(std::process::Stdio::null()).stderr(std::process::Stdio::null());
+            let _ = remove.status().await;
         }
 
-        // Add dtype if specified
-        if let Some(dtype) = config.dtype {
-            if dtype != "auto" {
-                cmd.arg("--dtype").arg(dtype);
-            }
+        let (program, args) = config.launcher.command_line(&config);
+        let cmd = config.launcher.command(&config);
+        let mut process = Self::start(cmd, config)?;
+
+        if let Some(state_dir) = &process.config.state_dir {
+            let mut record = EngineRecord {
+                pid: process.pid(),
+                host: process.config.host.clone(),
+                port: process.port,
+                command: redact_env(std::iter::once(program.display().to_string()).chain(args)),
+                owner_pid: 0,
+                owner_start: None,
+                path: EngineRecord::path(state_dir, &process.config),
+            };
+            record.claim();
+
+            // On failure the process is dropped, which kills it
+            record.write()?;
+            process.pidfile = Some(record);
         }
 
-        // Spawn the process
-        let child = cmd.spawn()
-            .map_err(|e| AxonError::ModelLoadFailed(format!("Failed to spawn vLLM: {}", e)))?;
+        Ok(process)
+    }
+
+    /// Start `cmd`, tracking startup progress in its output
+    fn start(cmd: Command, config: VllmConfig) -> Result<Self> {
+        let port = config.port
+            .ok_or_else(|| AxonError::InvalidConfig("vLLM port was not resolved".into()))?;
+
+        let startup = Arc::new(StartupTracker::new());
+        let mut sinks = config.log_sinks.clone();
+        sinks.push(startup.clone());
+        let engine = EngineProcess::start(cmd, "vLLM", sinks, config.log_tail_lines)?;
 
         Ok(Self {
-            pid: Some(child.id()),
+            engine,
+            port,
+            startup,
+            config,
+            pidfile: None,
         })
     }
 
+    /// The most recent lines the engine wrote to stdout/stderr
+    pub fn recent_output(&self) -> Vec<String> {
+        self.engine.recent_output()
+    }
+
+    /// Startup phase inferred from the engine's log output
+    pub fn startup_phase(&self) -> StartupPhase {
+        self.startup.phase()
+    }
+
+    /// Receiver that observes startup phase changes
+    pub fn startup_phases(&self) -> watch::Receiver<StartupPhase> {
+        self.startup.subscribe()
+    }
+
     /// Check if the process is still running
     pub fn is_running(&self) -> bool {
-        if let Some(pid) = self.pid {
-            // Try to send signal 0 to check if process exists
-            unsafe {
-                let result = libc::kill(pid as i32, 0);
-                result == 0 || (result == -1 && std::io::Error::last_os_error().raw_os_error() != Some(libc::ESRCH))
-            }
-        } else {
-            false
-        }
+        self.engine.is_running()
+    }
+
+    /// How the process ended, or `None` while it is still running
+    pub fn exit_status(&self) -> Option<ProcessExit> {
+        self.engine.exit_status()
+    }
+
+    /// Wait for the process to exit
+    pub async fn wait(&self) -> ProcessExit {
+        self.engine.wait().await
     }
 
     /// Wait until vLLM is ready to serve requests
+    ///
+    /// Polls `ready_probe_path` every `ready_poll_interval` until it answers
+    /// with a success status. Fails as soon as the process exits, once
+    /// `ready_timeout` passes without startup progress in the engine's logs,
+    /// or once `ready_deadline` passes in total.
     pub async fn wait_until_ready(&self) -> Result<()> {
-        let url = format!("http://{}:{}/health", self.host(), self.port());
-        let client = reqwest::Client::new();
-
-        for _ in 0..60 {
-            sleep(Duration::from_secs(2)).await;
-
-            match client.get(&url).send().await {
-                Ok(resp) if resp.status().is_success() => {
-                    return Ok(());
-                }
-                Ok(_) => continue,
-                Err(_) => continue,
+        let url = format!("{}{}", self.base_url(), self.config.ready_probe_path);
+        let client = reqwest::Client::builder()
+            .timeout(self.config.ready_poll_interval.max(Duration::from_secs(5)))
+            .build()?;
+        let deadline = Instant::now() + self.config.ready_deadline;
+
+        loop {
+            if let Some(exit) = self.exit_status() {
+                return Err(AxonError::ModelLoadFailed(logs::with_tail(
+                    format!("vLLM exited during startup ({})", exit),
+                    &self.recent_output(),
+                )));
+            }
+
+            if let Ok(resp) = client.get(&url).send().await
+                && resp.status().is_success()
+            {
+                return Ok(());
+            }
+
+            // An engine that keeps logging progress may still never come up
+            if Instant::now() >= deadline {
+                return Err(AxonError::ModelLoadFailed(logs::with_tail(
+                    format!(
+                        "vLLM was not ready after {}s while {} (probing {})",
+                        self.config.ready_deadline.as_secs(),
+                        self.startup_phase(),
+                        url
+                    ),
+                    &self.recent_output(),
+                )));
+            }
+
+            // Large models take a long time to load; otherwise only give up on a stall
+            if Instant::now() >= self.startup.last_progress() + self.config.ready_timeout {
+                return Err(AxonError::ModelLoadFailed(logs::with_tail(
+                    format!(
+                        "vLLM made no startup progress for {}s while {} (probing {})",
+                        self.config.ready_timeout.as_secs(),
+                        self.startup_phase(),
+                        url
+                    ),
+                    &self.recent_output(),
+                )));
+            }
+
+            // Wake early if the process dies between probes
+            tokio::select! {
+                _ = sleep(self.config.ready_poll_interval) => {}
+                _ = self.wait() => {}
             }
         }
+    }
 
-        Err(AxonError::ModelLoadFailed("vLLM did not become ready in time".into()))
+    /// Configuration the process was started with
+    pub fn config(&self) -> &VllmConfig {
+        &self.config
     }
 
-    /// Get the host the process is listening on
-    fn host(&self) -> String {
-        "127.0.0.1".to_string()
+    /// Process ID of the engine, which also leads its process group
+    pub fn pid(&self) -> u32 {
+        self.engine.pid()
     }
 
-    /// Get the port the process is listening on
-    fn port(&self) -> u16 {
-        8000
+    /// Port the server listens on
+    pub fn port(&self) -> u16 {
+        self.port
     }
 
-    /// Terminate the vLLM process
-    pub async fn terminate(self) -> Result<()> {
-        if let Some(pid) = self.pid {
-            unsafe {
-                libc::kill(pid as i32, libc::SIGTERM);
-            }
+    /// Base URL of the server's HTTP API
+    pub fn base_url(&self) -> String {
+        format!("http://{}:{}", self.config.connect_host(), self.port)
+    }
 
-            // Give process time to terminate gracefully
-            sleep(Duration::from_secs(5)).await;
+    /// Terminate the vLLM process and its workers
+    ///
+    /// Sends SIGTERM to the process group, then SIGKILL if any member is
+    /// still alive after a grace period, and returns once the engine has
+    /// been reaped.
+    pub async fn terminate(self) -> Result<ProcessExit> {
+        let deadline = Instant::now() + TERMINATE_GRACE;
 
-            // Force kill if still running
-            if self.is_running() {
-                unsafe {
-                    libc::kill(pid as i32, libc::SIGKILL);
-                }
-            }
+        // The runtime CLI exits once the container it runs has stopped
+        if let VllmLauncher::Container(container) = &self.config.launcher {
+            let stop = container.stop_command(&self.config, TERMINATE_GRACE);
+            let _ = timeout_at(deadline, Command::from(stop).status()).await;
+        }
+        Ok(self.engine.terminate(deadline.saturating_duration_since(Instant::now())).await)
+    }
+}
+
+impl Drop for VllmProcess {
+    fn drop(&mut self) {
+        // Killing the runtime CLI would leave its container running; the
+        // engine's own process group is killed when `engine` is dropped
+        if self.is_running()
+            && let VllmLauncher::Container(container) = &self.config.launcher
+        {
+            let _ = container.kill_command(&self.config)
+                .stdout(std::process::Stdio::null())
+                .stderr(std::process::Stdio::null())
+                .status();
+        }
+
+        if let Some(record) = &self.pidfile {
+            record.remove();
         }
-        Ok(())
     }
 }
 
@@ -124,18 +252,148 @@ impl VllmProcess {
 mod tests {
     use super::*;
 
+    fn shell(script: &str) -> Command {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(script);
+        cmd
+    }
+
+    #[tokio::test]
+    async fn test_wait_fails_fast_when_process_exits() {
+        let config = VllmConfig {
+            port: Some(1),
+            ready_timeout: Duration::from_secs(60),
+            ready_poll_interval: Duration::from_millis(50),
+            ..Default::default()
+        };
+        let process = VllmProcess::start(shell("echo 'CUDA out of memory' >&2; exit 3"), config).unwrap();
+
+        let started = std::time::Instant::now();
+        let err = process.wait_until_ready().await.unwrap_err().to_string();
+        assert!(err.contains("exit code 3"), "{}", err);
+        assert!(started.elapsed() < Duration::from_secs(10));
+        assert!(!process.is_running());
+    }
+
+    #[tokio::test]
+    async fn test_ready_timeout_resets_on_progress() {
+        let config = VllmConfig {
+            port: Some(1),
+            ready_timeout: Duration::from_millis(300),
+            ready_poll_interval: Duration::from_millis(20),
+            ..Default::default()
+        };
+        let script = "for i in 1 2 3 4 5 6; do echo \"Loading shards: ${i}0% | $i/10\"; sleep 0.1; done; sleep 30";
+        let process = VllmProcess::start(shell(script), config).unwrap();
+
+        let started = std::time::Instant::now();
+        let err = process.wait_until_ready().await.unwrap_err().to_string();
+        assert!(err.contains("no startup progress"), "{}", err);
+        assert!(started.elapsed() >= Duration::from_millis(600));
+        process.terminate().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_ready_deadline_bounds_progressing_startup() {
+        let config = VllmConfig {
+            port: Some(1),
+            ready_timeout: Duration::from_secs(60),
+            ready_deadline: Duration::from_millis(400),
+            ready_poll_interval: Duration::from_millis(20),
+            ..Default::default()
+        };
+        let script = "while true; do echo 'Loading shards: 10% | 1/10'; sleep 0.05; done";
+        let process = VllmProcess::start(shell(script), config).unwrap();
+
+        let started = std::time::Instant::now();
+        let err = process.wait_until_ready().await.unwrap_err().to_string();
+        assert!(err.contains("not ready after"), "{}", err);
+        assert!(started.elapsed() < Duration::from_secs(10));
+        process.terminate().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_exit_status_reports_signal() {
+        let process = VllmProcess::start(shell("sleep 30"), VllmConfig::default()).unwrap();
+        assert!(process.is_running());
+        assert_eq!(process.exit_status(), None);
+
+        let exit = process.terminate().await.unwrap();
+        assert_eq!(exit, ProcessExit { code: None, signal: Some(libc::SIGTERM) });
+    }
+
+    #[cfg(target_os = "linux")]
+    #[tokio::test]
+    async fn test_terminate_kills_process_group() {
+        let process = VllmProcess::start(shell("sleep 30 & echo $!; wait"), VllmConfig::default()).unwrap();
+
+        let worker = loop {
+            if let Some(line) = process.recent_output().first() {
+                break line.parse::<u32>().unwrap();
+            }
+            sleep(Duration::from_millis(10)).await;
+        };
+
+        process.terminate().await.unwrap();
+
+        // Orphans may linger as zombies if nothing reaps them; those are dead too
+        let stat = std::fs::read_to_string(format!("/proc/{}/stat", worker)).unwrap_or_default();
+        assert!(stat.is_empty() || stat.contains(") Z "), "worker survived: {}", stat);
+    }
+
+    #[tokio::test]
+    async fn test_spawn_with_fake_executable() {
+        use crate::vllm::launcher::VllmLauncher;
+
+        let dir = std::env::temp_dir().join(format!("axon-launcher-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let fake = dir.join("fake-vllm");
+        std::fs::write(&fake, "echo \"args: $*\"\necho \"token: $HF_TOKEN\"\necho \"cwd: $(pwd)\"\n").unwrap();
+
+        let config = VllmConfig {
+            model_name: "m".to_string(),
+            port: None,
+            // Run through `sh` so the script never needs exec permission
+            launcher: VllmLauncher::Custom {
+                program: "sh".into(),
+                args: vec![fake.display().to_string(), "serve".to_string()],
+            },
+            env: vec![("HF_TOKEN".to_string(), "secret".to_string())],
+            working_dir: Some(dir.clone()),
+            ..Default::default()
+        };
+        let process = VllmProcess::spawn(config).await.unwrap();
+        assert_eq!(process.wait().await.code, Some(0));
+
+        // Output is drained by separate tasks that may still be catching up
+        let mut output = Vec::new();
+        for _ in 0..100 {
+            output = process.recent_output();
+            if output.len() == 3 {
+                break;
+            }
+            sleep(Duration::from_millis(10)).await;
+        }
+
+        assert!(output[0].starts_with("args: serve --model m --host 127.0.0.1"), "{:?}", output);
+        assert_eq!(output[1], "token: secret");
+        assert_eq!(output[2], format!("cwd: {}", dir.display()));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_vllm_config() {
         let config = VllmConfig {
             model_name: "test-model".to_string(),
             host: "127.0.0.1".to_string(),
-            port: 8000,
+            port: Some(8000),
             tensor_parallel_size: Some(1),
             max_sequence_length: Some(2048),
             dtype: Some("auto".to_string()),
+            ..Default::default()
         };
 
         assert_eq!(config.model_name, "test-model");
-        assert_eq!(config.port, 8000);
+        assert_eq!(config.port, Some(8000));
     }
 }
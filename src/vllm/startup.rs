@@ -0,0 +1,162 @@
+//! Startup progress parsed from vLLM's log output
+//!
+//! vLLM does not expose its startup state over HTTP until the server is up,
+//! so the phase is inferred from well-known log lines. The matching is
+//! deliberately loose; log wording varies between vLLM versions.
+
+use crate::logs::{LogSink, LogStream};
+use std::fmt;
+use std::sync::Mutex;
+use tokio::sync::watch;
+use tokio::time::Instant;
+
+/// Where a starting vLLM server is in its startup sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum StartupPhase {
+    /// Process started; nothing recognizable logged yet
+    Launching,
+    /// Fetching weights from the Hugging Face Hub
+    DownloadingWeights,
+    /// Loading weights onto the GPUs
+    LoadingWeights,
+    /// Profiling memory to size the KV cache
+    ProfilingMemory,
+    /// Capturing CUDA graphs
+    CapturingCudaGraphs,
+    /// HTTP server is up
+    Serving,
+}
+
+impl StartupPhase {
+    /// The phase a log line indicates, if any
+    pub fn from_log_line(line: &str) -> Option<Self> {
+        let line = line.to_ascii_lowercase();
+        let has = |patterns: &[&str]| patterns.iter().any(|p| line.contains(p));
+
+        if has(&["uvicorn running on", "application startup complete", "starting vllm api server"]) {
+            Some(Self::Serving)
+        } else if has(&["capturing cudagraph", "capturing cuda graph", "capturing the model for cuda graphs"]) {
+            Some(Self::CapturingCudaGraphs)
+        } else if has(&["memory profiling", "# gpu blocks", "kv cache memory", "gpu kv cache size"]) {
+            Some(Self::ProfilingMemory)
+        } else if has(&["starting to load model", "loading weights", "loading safetensors checkpoint", "model loading took"]) {
+            Some(Self::LoadingWeights)
+        } else if has(&["downloading", "fetching"]) {
+            Some(Self::DownloadingWeights)
+        } else {
+            None
+        }
+    }
+
+    /// Human-readable phase name
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Launching => "launching",
+            Self::DownloadingWeights => "downloading weights",
+            Self::LoadingWeights => "loading weights",
+            Self::ProfilingMemory => "profiling memory",
+            Self::CapturingCudaGraphs => "capturing CUDA graphs",
+            Self::Serving => "serving",
+        }
+    }
+}
+
+impl fmt::Display for StartupPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Log sink that follows startup phases and records when progress was made
+///
+/// Progress is a move to a later phase or a progress-bar update (such as a
+/// download or shard-loading bar). Phases only move forward, since
+/// tensor-parallel workers may log the same step at different times.
+#[derive(Debug)]
+pub(crate) struct StartupTracker {
+    phase: watch::Sender<StartupPhase>,
+    last_progress: Mutex<Instant>,
+}
+
+impl StartupTracker {
+    pub(crate) fn new() -> Self {
+        Self {
+            phase: watch::Sender::new(StartupPhase::Launching),
+            last_progress: Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Receiver that observes phase changes
+    pub(crate) fn subscribe(&self) -> watch::Receiver<StartupPhase> {
+        self.phase.subscribe()
+    }
+
+    /// Current phase
+    pub(crate) fn phase(&self) -> StartupPhase {
+        *self.phase.borrow()
+    }
+
+    /// When progress was last observed
+    pub(crate) fn last_progress(&self) -> Instant {
+        *self.last_progress.lock().unwrap()
+    }
+}
+
+impl LogSink for StartupTracker {
+    fn write_line(&self, _stream: LogStream, line: &str) {
+        let advanced = StartupPhase::from_log_line(line)
+            .is_some_and(|next| self.phase.send_if_modified(|phase| {
+                let advance = next > *phase;
+                if advance {
+                    *phase = next;
+                }
+                advance
+            }));
+        let progress_bar = line.contains('%') && line.contains('|');
+
+        if advanced || progress_bar {
+            *self.last_progress.lock().unwrap() = Instant::now();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_phases_from_vllm_logs() {
+        let cases = [
+            ("INFO 05-01 12:00:00 weight_utils.py:243] Downloading 'model-00001-of-00004.safetensors'", StartupPhase::DownloadingWeights),
+            ("INFO gpu_model_runner.py:1329] Starting to load model meta-llama/Llama-3.1-8B...", StartupPhase::LoadingWeights),
+            ("Loading safetensors checkpoint shards:  50% Completed | 2/4 [00:03<00:03,  1.60s/it]", StartupPhase::LoadingWeights),
+            ("INFO worker.py:267] Memory profiling takes 2.13 seconds", StartupPhase::ProfilingMemory),
+            ("INFO kv_cache_utils.py:634] GPU KV cache size: 226,224 tokens", StartupPhase::ProfilingMemory),
+            ("Capturing CUDA graph shapes: 100%|██████████| 67/67 [00:21<00:00,  3.10it/s]", StartupPhase::CapturingCudaGraphs),
+            ("INFO:     Uvicorn running on http://0.0.0.0:8000 (Press CTRL+C to quit)", StartupPhase::Serving),
+        ];
+
+        for (line, phase) in cases {
+            assert_eq!(StartupPhase::from_log_line(line), Some(phase), "{}", line);
+        }
+        assert_eq!(StartupPhase::from_log_line("WARNING some unrelated warning"), None);
+    }
+
+    #[test]
+    fn test_tracker_only_moves_forward() {
+        let tracker = StartupTracker::new();
+        let started = tracker.last_progress();
+
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        tracker.write_line(LogStream::Stderr, "Memory profiling takes 2.13 seconds");
+        assert_eq!(tracker.phase(), StartupPhase::ProfilingMemory);
+        assert!(tracker.last_progress() > started);
+
+        let profiled = tracker.last_progress();
+        std::thread::sleep(std::time::Duration::from_millis(5));
+        tracker.write_line(LogStream::Stderr, "Loading weights took 14.99 GiB");
+        tracker.write_line(LogStream::Stderr, "WARNING unrelated");
+        assert_eq!(tracker.phase(), StartupPhase::ProfilingMemory);
+        assert_eq!(tracker.last_progress(), profiled);
+    }
+}
@@ -0,0 +1,358 @@
+//! Supervision and automatic restart of a spawned vLLM server
+//!
+//! A `Supervisor` owns the running `VllmProcess` from a background task.
+//! When the engine exits, the task consults the configured `RestartPolicy`,
+//! backs off exponentially, and spawns a replacement on the same port so
+//! existing clients reconnect without being rebuilt. Every transition is
+//! published as a `LifecycleEvent`.
+
+use crate::backend::ProcessExit;
+use crate::error::Result;
+use std::collections::VecDeque;
+use std::time::Duration;
+use tokio::sync::{broadcast, watch};
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, Instant};
+
+use super::config::VllmConfig;
+use super::process::VllmProcess;
+use super::startup::StartupPhase;
+
+/// When a supervised engine is restarted after it exits
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RestartPolicy {
+    /// Never restart; the backend reports `Failed` once the engine exits
+    #[default]
+    Never,
+    /// Restart unless the engine exited with status 0
+    OnFailure,
+    /// Restart whenever the engine exits
+    Always,
+}
+
+impl RestartPolicy {
+    /// Whether an engine that ended with `exit` should be restarted
+    pub fn should_restart(&self, exit: &ProcessExit) -> bool {
+        match self {
+            Self::Never => false,
+            Self::OnFailure => exit.code != Some(0),
+            Self::Always => true,
+        }
+    }
+}
+
+/// A change in a supervised engine's lifecycle
+#[derive(Debug, Clone, PartialEq)]
+pub enum LifecycleEvent {
+    /// An engine process was started
+    Spawned {
+        /// Process ID of the new engine
+        pid: u32,
+    },
+    /// The starting engine moved to a new startup phase
+    Startup(StartupPhase),
+    /// The engine passed its readiness probe
+    Ready {
+        /// Base URL the engine is serving on
+        base_url: String,
+    },
+    /// The engine exited
+    Exited(ProcessExit),
+    /// A restart is scheduled after `delay`
+    Restarting {
+        /// Restart number within the current restart window, from 1
+        attempt: usize,
+        /// Backoff before the engine is spawned again
+        delay: Duration,
+    },
+    /// A restart attempt failed to spawn or become ready
+    RestartFailed(String),
+    /// The restart limit was reached; the engine stays down
+    GaveUp {
+        /// Restarts made within the restart window
+        restarts: usize,
+    },
+    /// The engine was stopped by `shutdown`
+    Stopped,
+}
+
+/// Current state of a supervised engine
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SupervisorState {
+    /// The engine is up and serving
+    Running,
+    /// The engine exited and a replacement is being started
+    Restarting,
+    /// The engine exited and will not be restarted
+    Failed(ProcessExit),
+    /// The engine was stopped deliberately
+    Stopped,
+}
+
+/// Owns a vLLM process and restarts it according to its config
+///
+/// Dropping the supervisor stops the engine in the background.
+pub struct Supervisor {
+    /// Address the engine serves on; stable across restarts
+    base_url: String,
+
+    /// Latest state, published by the supervision task
+    state: watch::Receiver<SupervisorState>,
+
+    /// Set to `true` to stop the engine; dropping it has the same effect
+    stop: watch::Sender<bool>,
+
+    /// The supervision task
+    task: JoinHandle<()>,
+}
+
+impl Supervisor {
+    /// Spawn vLLM, wait until it is ready, and start supervising it
+    ///
+    /// Failures during this first start are returned rather than retried.
+    pub async fn start(mut config: VllmConfig, events: broadcast::Sender<LifecycleEvent>) -> Result<Self> {
+        // Pin the port so restarts come back at the same address
+        config.port = Some(config.reserve_port()?);
+
+        let process = start_ready(config.clone(), &events).await?;
+        Ok(Self::supervise(process, config, events))
+    }
+
+    /// Supervise an engine that is already running
+    fn supervise(process: VllmProcess, config: VllmConfig, events: broadcast::Sender<LifecycleEvent>) -> Self {
+        let base_url = process.base_url();
+        let (state_tx, state) = watch::channel(SupervisorState::Running);
+        let (stop, stop_rx) = watch::channel(false);
+
+        let task = tokio::spawn(run(process, config, events, state_tx, stop_rx));
+
+        Self {
+            base_url,
+            state,
+            stop,
+            task,
+        }
+    }
+
+    /// Base URL of the engine's HTTP API
+    pub fn base_url(&self) -> String {
+        self.base_url.clone()
+    }
+
+    /// Current state of the engine
+    pub fn state(&self) -> SupervisorState {
+        *self.state.borrow()
+    }
+
+    /// Stop the engine and wait for the supervision task to finish
+    pub async fn shutdown(self) -> Result<()> {
+        let _ = self.stop.send(true);
+        let _ = self.task.await;
+        Ok(())
+    }
+}
+
+/// Limits how often an engine is restarted
+#[derive(Debug, Default)]
+struct RestartBudget {
+    /// When each restart within the window was made
+    restarts: VecDeque<Instant>,
+}
+
+impl RestartBudget {
+    /// Record a restart at `now`, returning its attempt number and backoff,
+    /// or `None` if `max_restarts` have already been made within the window
+    fn next(&mut self, config: &VllmConfig, now: Instant) -> Option<(usize, Duration)> {
+        while self.restarts.front().is_some_and(|t| now.duration_since(*t) > config.restart_window) {
+            self.restarts.pop_front();
+        }
+        if self.restarts.len() >= config.max_restarts {
+            return None;
+        }
+
+        self.restarts.push_back(now);
+        let attempt = self.restarts.len();
+        let factor = 2u32.saturating_pow(attempt as u32 - 1);
+        let delay = config.restart_backoff.saturating_mul(factor).min(config.restart_backoff_max);
+        Some((attempt, delay))
+    }
+
+    fn len(&self) -> usize {
+        self.restarts.len()
+    }
+}
+
+/// Spawn vLLM and wait for it to become ready, cleaning up on failure
+async fn start_ready(config: VllmConfig, events: &broadcast::Sender<LifecycleEvent>) -> Result<VllmProcess> {
+    let process = VllmProcess::spawn(config).await?;
+    let _ = events.send(LifecycleEvent::Spawned { pid: process.pid() });
+
+    // Forward startup phases while waiting for readiness
+    let result = {
+        let mut phases = process.startup_phases();
+        let ready = process.wait_until_ready();
+        tokio::pin!(ready);
+
+        loop {
+            tokio::select! {
+                result = &mut ready => break result,
+                Ok(()) = phases.changed() => {
+                    let _ = events.send(LifecycleEvent::Startup(*phases.borrow_and_update()));
+                }
+            }
+        }
+    };
+
+    // Don't leak the engine if it never becomes ready
+    if let Err(e) = result {
+        let _ = process.terminate().await;
+        return Err(e);
+    }
+
+    let _ = events.send(LifecycleEvent::Ready {
+        base_url: process.base_url(),
+    });
+    Ok(process)
+}
+
+/// Resolves once a stop is requested or the `Supervisor` is dropped
+async fn stopped(stop: &mut watch::Receiver<bool>) {
+    let _ = stop.wait_for(|stop| *stop).await;
+}
+
+/// Supervision loop: wait for the engine to exit, then restart or give up
+async fn run(
+    mut process: VllmProcess,
+    config: VllmConfig,
+    events: broadcast::Sender<LifecycleEvent>,
+    state: watch::Sender<SupervisorState>,
+    mut stop: watch::Receiver<bool>,
+) {
+    let mut budget = RestartBudget::default();
+
+    loop {
+        let exit = tokio::select! {
+            exit = process.wait() => exit,
+            _ = stopped(&mut stop) => {
+                let _ = process.terminate().await;
+                break;
+            }
+        };
+
+        let _ = events.send(LifecycleEvent::Exited(exit));
+        if !config.restart_policy.should_restart(&exit) {
+            state.send_replace(SupervisorState::Failed(exit));
+            return;
+        }
+        state.send_replace(SupervisorState::Restarting);
+
+        // Keep trying until a replacement is ready or the budget runs out
+        process = loop {
+            let Some((attempt, delay)) = budget.next(&config, Instant::now()) else {
+                let _ = events.send(LifecycleEvent::GaveUp { restarts: budget.len() });
+                state.send_replace(SupervisorState::Failed(exit));
+                return;
+            };
+            let _ = events.send(LifecycleEvent::Restarting { attempt, delay });
+
+            // A replacement dropped mid-start is killed by `VllmProcess`'s Drop
+            let result = tokio::select! {
+                result = async {
+                    sleep(delay).await;
+                    start_ready(config.clone(), &events).await
+                } => result,
+                _ = stopped(&mut stop) => {
+                    state.send_replace(SupervisorState::Stopped);
+                    let _ = events.send(LifecycleEvent::Stopped);
+                    return;
+                }
+            };
+
+            match result {
+                Ok(process) => break process,
+                Err(e) => {
+                    let _ = events.send(LifecycleEvent::RestartFailed(e.to_string()));
+                }
+            }
+        };
+
+        state.send_replace(SupervisorState::Running);
+    }
+
+    state.send_replace(SupervisorState::Stopped);
+    let _ = events.send(LifecycleEvent::Stopped);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vllm::launcher::VllmLauncher;
+
+    fn exit(code: i32) -> ProcessExit {
+        ProcessExit { code: Some(code), signal: None }
+    }
+
+    #[test]
+    fn test_restart_policy() {
+        assert!(!RestartPolicy::Never.should_restart(&exit(1)));
+        assert!(RestartPolicy::OnFailure.should_restart(&exit(1)));
+        assert!(!RestartPolicy::OnFailure.should_restart(&exit(0)));
+        assert!(RestartPolicy::Always.should_restart(&exit(0)));
+    }
+
+    #[test]
+    fn test_restart_budget_backoff_and_window() {
+        let config = VllmConfig {
+            restart_backoff: Duration::from_secs(1),
+            restart_backoff_max: Duration::from_secs(3),
+            max_restarts: 3,
+            restart_window: Duration::from_secs(60),
+            ..Default::default()
+        };
+        let mut budget = RestartBudget::default();
+        let start = Instant::now();
+
+        assert_eq!(budget.next(&config, start), Some((1, Duration::from_secs(1))));
+        assert_eq!(budget.next(&config, start), Some((2, Duration::from_secs(2))));
+        assert_eq!(budget.next(&config, start), Some((3, Duration::from_secs(3))));
+        assert_eq!(budget.next(&config, start), None);
+
+        // Restarts older than the window no longer count
+        let later = start + Duration::from_secs(61);
+        assert_eq!(budget.next(&config, later), Some((1, Duration::from_secs(1))));
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_failed_restarts() {
+        let config = VllmConfig {
+            port: None,
+            launcher: VllmLauncher::Custom {
+                program: "sh".into(),
+                args: vec!["-c".to_string(), "exit 2".to_string()],
+            },
+            restart_policy: RestartPolicy::OnFailure,
+            restart_backoff: Duration::from_millis(10),
+            max_restarts: 1,
+            ready_poll_interval: Duration::from_millis(10),
+            ..Default::default()
+        };
+        let (events, mut rx) = broadcast::channel(16);
+
+        let process = VllmProcess::spawn(config.clone()).await.unwrap();
+        let supervisor = Supervisor::supervise(process, config, events);
+
+        let mut state = supervisor.state.clone();
+        state.wait_for(|s| matches!(s, SupervisorState::Failed(_))).await.unwrap();
+        assert_eq!(supervisor.state(), SupervisorState::Failed(exit(2)));
+
+        let mut seen = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            seen.push(event);
+        }
+        assert_eq!(seen[0], LifecycleEvent::Exited(exit(2)));
+        assert!(matches!(seen[1], LifecycleEvent::Restarting { attempt: 1, .. }));
+        assert!(matches!(seen.last(), Some(LifecycleEvent::GaveUp { restarts: 1 })));
+
+        supervisor.shutdown().await.unwrap();
+    }
+}